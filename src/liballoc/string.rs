@@ -279,6 +279,7 @@ use crate::vec::Vec;
 /// [`as_str()`]: struct.String.html#method.as_str
 #[derive(PartialOrd, Eq, Ord)]
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "string_type")]
 pub struct String {
     vec: Vec<u8>,
 }
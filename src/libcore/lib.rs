@@ -138,6 +138,7 @@
 #![feature(const_type_id)]
 #![feature(const_caller_location)]
 #![feature(assoc_int_consts)]
+#![cfg_attr(not(bootstrap), feature(must_not_suspend))]
 
 #[prelude_import]
 #[allow(unused)]
@@ -207,7 +207,9 @@ impl<'a, 'hir> NodeCollector<'a, 'hir> {
         // don't have to include the path prefix remapping commandline args.
         // If we included the full mapping in the SVH, we could only have
         // reproducible builds by compiling from the same directory. So we just
-        // hash the result of the mapping instead of the mapping itself.
+        // hash the result of the mapping instead of the mapping itself. The
+        // names which were not remapped are hashed relative to the workspace
+        // root, for the same reason (see `FilePathMapping::stable_name`).
         let mut source_file_names: Vec<_> = self
             .source_map
             .files()
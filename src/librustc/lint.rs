@@ -23,6 +23,9 @@ pub enum LintSource {
 
     /// Lint level was set by a command-line flag.
     CommandLine(Symbol),
+
+    /// Lint level was set by the lint configuration file (`--lint-config`).
+    LintConfig(Symbol),
 }
 
 pub type LevelSource = (Level, LintSource);
@@ -251,6 +254,18 @@ pub fn struct_lint_level<'a>(
                 );
             }
         }
+        LintSource::LintConfig(lint_config_name) => {
+            let level_str = level.as_str();
+            let msg = if lint_config_name.as_str() == name {
+                format!("`{} {}` set in the lint configuration file", level_str, name)
+            } else {
+                format!(
+                    "`{} {}` implied by `{} {}` in the lint configuration file",
+                    level_str, name, level_str, lint_config_name
+                )
+            };
+            sess.diag_note_once(&mut err, DiagnosticMessageId::from(lint), &msg);
+        }
         LintSource::Node(lint_attr_name, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(&rationale.as_str());
@@ -49,7 +49,7 @@ use rustc_data_structures::stable_hasher::{
     hash_stable_hashmap, HashStable, StableHasher, StableVec,
 };
 use rustc_data_structures::sync::{self, Lock, Lrc, WorkerLocal};
-use rustc_errors::DiagnosticBuilder;
+use rustc_errors::{DiagnosticBuilder, DiagnosticItem};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, DefIdSet, DefIndex, LOCAL_CRATE};
@@ -2607,7 +2607,11 @@ impl<'tcx> TyCtxt<'tcx> {
         msg: &str,
     ) -> DiagnosticBuilder<'tcx> {
         let (level, src) = self.lint_level_at_node(lint, hir_id);
-        struct_lint_level(self.sess, lint, level, src, Some(span.into()), msg)
+        let mut err = struct_lint_level(self.sess, lint, level, src, Some(span.into()), msg);
+        if level != Level::Allow {
+            err.set_item(self.diagnostic_location(hir_id));
+        }
+        err
     }
 
     pub fn struct_lint_node(
@@ -2617,7 +2621,37 @@ impl<'tcx> TyCtxt<'tcx> {
         msg: &str,
     ) -> DiagnosticBuilder<'tcx> {
         let (level, src) = self.lint_level_at_node(lint, id);
-        struct_lint_level(self.sess, lint, level, src, None, msg)
+        let mut err = struct_lint_level(self.sess, lint, level, src, None, msg);
+        if level != Level::Allow {
+            err.set_item(self.diagnostic_location(id));
+        }
+        err
+    }
+
+    /// Returns the item containing `hir_id` and its module, which tell the
+    /// diagnostics about it apart across compilations. The def paths are
+    /// built from the HIR alone, so that no query is run while a diagnostic
+    /// is created.
+    pub fn diagnostic_location(self, hir_id: HirId) -> DiagnosticItem {
+        let def_path_str = |def_id| {
+            let path = self.def_path(def_id);
+            if path.data.is_empty() {
+                return "crate".to_string();
+            }
+            let segments: Vec<_> = path
+                .data
+                .iter()
+                .map(|segment| match segment.disambiguator {
+                    0 => segment.data.as_symbol().to_string(),
+                    n => format!("{}[{}]", segment.data.as_symbol(), n),
+                })
+                .collect();
+            segments.join("::")
+        };
+        DiagnosticItem {
+            path: def_path_str(DefId::local(hir_id.owner)),
+            module: def_path_str(self.hir().get_module_parent(hir_id)),
+        }
     }
 
     pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx StableVec<TraitCandidate>> {
@@ -4,6 +4,7 @@ use crate::util::check_builtin_macro_attribute;
 
 use rustc_ast_pretty::pprust;
 use rustc_expand::base::*;
+use rustc_session::parse::feature_err;
 use rustc_span::source_map::respan;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
@@ -149,6 +150,13 @@ pub fn expand_test_or_bench(
             ],
         )
     } else {
+        // $test_fn()
+        let mut test_call = cx.expr_call(sp, cx.expr_path(cx.path(sp, vec![item.ident])), vec![]);
+        if is_async_fn(&item) {
+            // test::block_on($test_fn())
+            test_call = cx.expr_call(sp, cx.expr_path(test_path("block_on")), vec![test_call]);
+        }
+
         cx.expr_call(
             sp,
             cx.expr_path(test_path("StaticTestFn")),
@@ -160,10 +168,7 @@ pub fn expand_test_or_bench(
                     cx.expr_call(
                         sp,
                         cx.expr_path(test_path("assert_test_result")),
-                        vec![
-                            // $test_fn()
-                            cx.expr_call(sp, cx.expr_path(cx.path(sp, vec![item.ident])), vec![]), // )
-                        ],
+                        vec![test_call], // )
                     ), // }
                 ), // )
             ],
@@ -253,6 +258,36 @@ pub fn expand_test_or_bench(
                                         }
                                     },
                                 ),
+                                // timeout: ...
+                                field(
+                                    "timeout",
+                                    match test_timeout(cx, &item) {
+                                        // Some(Duration::from_secs(...))
+                                        Some(secs) => cx.expr_some(
+                                            sp,
+                                            cx.expr_call_global(
+                                                sp,
+                                                cx.std_path(&[
+                                                    sym::time,
+                                                    sym::Duration,
+                                                    sym::from_secs,
+                                                ]),
+                                                vec![cx.expr_lit(
+                                                    sp,
+                                                    ast::LitKind::Int(
+                                                        secs as u128,
+                                                        ast::LitIntType::Unsigned(ast::UintTy::U64),
+                                                    ),
+                                                )],
+                                            ),
+                                        ),
+                                        // None
+                                        None => cx.expr_path(cx.path_global(
+                                            sp,
+                                            cx.std_path(&[sym::option, sym::Option, sym::None]),
+                                        )),
+                                    },
+                                ),
                                 // },
                             ],
                         ),
@@ -343,6 +378,22 @@ fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     }
 }
 
+/// Returns the number of seconds given to `#[test_timeout = "..."]`, if any.
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = attr::find_by_name(&i.attrs, sym::test_timeout)?;
+    match attr.value_str().and_then(|s| s.as_str().parse::<u64>().ok()) {
+        Some(secs) if secs > 0 => Some(secs),
+        _ => {
+            cx.parse_sess
+                .span_diagnostic
+                .struct_span_err(attr.span, "invalid `#[test_timeout]` attribute")
+                .help("expected a positive number of seconds: `#[test_timeout = \"30\"]`")
+                .emit();
+            None
+        }
+    }
+}
+
 enum TestType {
     UnitTest,
     IntegrationTest,
@@ -371,6 +422,13 @@ fn test_type(cx: &ExtCtxt<'_>) -> TestType {
     }
 }
 
+fn is_async_fn(i: &ast::Item) -> bool {
+    match i.kind {
+        ast::ItemKind::Fn(ref sig, ..) => sig.header.asyncness.node.is_async(),
+        _ => false,
+    }
+}
+
 fn has_test_signature(cx: &ExtCtxt<'_>, i: &ast::Item) -> bool {
     let has_should_panic_attr = attr::contains_name(&i.attrs, sym::should_panic);
     let ref sd = cx.parse_sess.span_diagnostic;
@@ -379,8 +437,11 @@ fn has_test_signature(cx: &ExtCtxt<'_>, i: &ast::Item) -> bool {
             sd.span_err(i.span, "unsafe functions cannot be used for tests");
             return false;
         }
-        if sig.header.asyncness.node.is_async() {
-            sd.span_err(i.span, "async functions cannot be used for tests");
+        if sig.header.asyncness.node.is_async()
+            && !cx.ecfg.features.map_or(false, |features| features.async_test)
+        {
+            feature_err(cx.parse_sess, sym::async_test, i.span, "async tests are experimental")
+                .emit();
             return false;
         }
 
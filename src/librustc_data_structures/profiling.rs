@@ -83,8 +83,10 @@
 
 use crate::fx::FxHashMap;
 
+use std::cell::Cell;
 use std::error::Error;
 use std::fs;
+use std::io::Write;
 use std::path::Path;
 use std::process;
 use std::sync::Arc;
@@ -92,7 +94,8 @@ use std::time::{Duration, Instant};
 use std::u32;
 
 use measureme::{EventId, EventIdBuilder, SerializableString, StringId};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rustc_serialize::json::as_json;
 
 /// MmapSerializatioSink is faster on macOS and Linux
 /// but FileSerializationSink is faster on Windows
@@ -165,6 +168,10 @@ pub struct SelfProfilerRef {
 
     // Print extra verbose generic activities to stdout
     print_extra_verbose_generic_activities: bool,
+
+    // Write a record for every verbose and extra verbose generic activity to
+    // the file given with -Ztime-passes-json
+    time_passes_report: Option<Arc<TimePassesReport>>,
 }
 
 impl SelfProfilerRef {
@@ -172,6 +179,7 @@ impl SelfProfilerRef {
         profiler: Option<Arc<SelfProfiler>>,
         print_verbose_generic_activities: bool,
         print_extra_verbose_generic_activities: bool,
+        time_passes_report: Option<Arc<TimePassesReport>>,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
         // ensuring that nothing ever tries to actually access it.
@@ -183,6 +191,7 @@ impl SelfProfilerRef {
             event_filter_mask,
             print_verbose_generic_activities,
             print_extra_verbose_generic_activities,
+            time_passes_report,
         }
     }
 
@@ -224,6 +233,7 @@ impl SelfProfilerRef {
         VerboseTimingGuard::start(
             event_id,
             self.print_verbose_generic_activities,
+            self.time_passes_report.as_deref(),
             self.generic_activity(event_id),
         )
     }
@@ -242,10 +252,20 @@ impl SelfProfilerRef {
         VerboseTimingGuard::start(
             event_id,
             self.print_extra_verbose_generic_activities,
+            self.time_passes_report.as_deref(),
             TimingGuard::none(),
         )
     }
 
+    /// Records the time of an activity that was measured by the caller in the
+    /// `-Z time-passes-json` report, if there is one. Printing it is left to
+    /// the caller.
+    pub fn record_time_passes_entry(&self, event_id: &str, dur: Duration) {
+        if let Some(report) = &self.time_passes_report {
+            report.record(event_id, TIME_DEPTH.with(|depth| depth.get()), dur, None);
+        }
+    }
+
     /// Start profiling a generic activity. Profiling continues until the
     /// TimingGuard returned from this call is dropped.
     #[inline(always)]
@@ -500,15 +520,26 @@ impl<'a> TimingGuard<'a> {
 pub struct VerboseTimingGuard<'a> {
     event_id: &'a str,
     start: Option<Instant>,
+    verbose: bool,
+    // The report to write a record to, with the RSS at the start
+    report: Option<(&'a TimePassesReport, Option<usize>)>,
     _guard: TimingGuard<'a>,
 }
 
 impl<'a> VerboseTimingGuard<'a> {
-    pub fn start(event_id: &'a str, verbose: bool, _guard: TimingGuard<'a>) -> Self {
+    pub fn start(
+        event_id: &'a str,
+        verbose: bool,
+        report: Option<&'a TimePassesReport>,
+        _guard: TimingGuard<'a>,
+    ) -> Self {
+        let report = report.map(|report| (report, report.start_pass()));
         VerboseTimingGuard {
             event_id,
             _guard,
-            start: if unlikely!(verbose) { Some(Instant::now()) } else { None },
+            start: if unlikely!(verbose || report.is_some()) { Some(Instant::now()) } else { None },
+            verbose,
+            report,
         }
     }
 
@@ -521,7 +552,73 @@ impl<'a> VerboseTimingGuard<'a> {
 
 impl Drop for VerboseTimingGuard<'_> {
     fn drop(&mut self) {
-        self.start.map(|start| print_time_passes_entry(true, self.event_id, start.elapsed()));
+        if let Some(start) = self.start {
+            let dur = start.elapsed();
+            print_time_passes_entry(self.verbose, self.event_id, dur);
+            if let Some((report, rss_start)) = self.report {
+                report.finish_pass(self.event_id, dur, rss_start);
+            }
+        }
+    }
+}
+
+thread_local! {
+    // The number of passes that are currently timed on this thread, for
+    // -Ztime-passes-json
+    static TIME_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// A line of the -Ztime-passes-json report.
+#[derive(RustcEncodable)]
+struct TimePassesRecord<'a> {
+    pass: &'a str,
+    /// The number of passes this one is nested in, on the same thread.
+    depth: usize,
+    /// Wall time in seconds.
+    time: f64,
+    /// Resident set size in bytes, if it is known.
+    rss_start: Option<usize>,
+    rss_end: Option<usize>,
+}
+
+/// Writes the time and memory usage of every pass reported by -Ztime-passes to
+/// a file, as one JSON object per line, so that they can be tracked by tools.
+///
+/// The timings of the individual LLVM passes (see -Ztime-llvm-passes) are
+/// printed by LLVM itself and are not part of the report.
+pub struct TimePassesReport {
+    file: Mutex<fs::File>,
+}
+
+impl TimePassesReport {
+    pub fn new(path: &Path) -> Result<TimePassesReport, Box<dyn Error>> {
+        Ok(TimePassesReport { file: Mutex::new(fs::File::create(path)?) })
+    }
+
+    fn start_pass(&self) -> Option<usize> {
+        TIME_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        get_resident()
+    }
+
+    fn finish_pass(&self, what: &str, dur: Duration, rss_start: Option<usize>) {
+        let depth = TIME_DEPTH.with(|depth| {
+            depth.set(depth.get() - 1);
+            depth.get()
+        });
+        self.record(what, depth, dur, rss_start);
+    }
+
+    fn record(&self, what: &str, depth: usize, dur: Duration, rss_start: Option<usize>) {
+        let record = TimePassesRecord {
+            pass: what,
+            depth,
+            time: dur.as_secs_f64(),
+            rss_start,
+            rss_end: get_resident(),
+        };
+        // Like the output of -Ztime-passes, the report is best effort and
+        // doesn't fail the compilation.
+        let _ = writeln!(self.file.lock(), "{}", as_json(&record));
     }
 }
 
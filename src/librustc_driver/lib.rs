@@ -408,6 +408,10 @@ pub fn run_compiler(
                 sess.code_stats.print_type_sizes();
             }
 
+            if sess.opts.debugging_opts.print_stack_frame_sizes {
+                sess.code_stats.print_stack_frame_sizes();
+            }
+
             let linker = queries.linker()?;
             Ok(Some(linker))
         })?;
@@ -7,6 +7,7 @@
 
 use crate::emitter::FileWithAnnotatedLines;
 use crate::snippet::Line;
+use crate::translation::Catalog;
 use crate::{CodeSuggestion, Diagnostic, DiagnosticId, Emitter, Level, SubDiagnostic};
 use annotate_snippets::display_list::DisplayList;
 use annotate_snippets::formatter::DisplayListFormatter;
@@ -24,11 +25,14 @@ pub struct AnnotateSnippetEmitterWriter {
     ui_testing: bool,
 
     macro_backtrace: bool,
+    catalog: Option<Lrc<Catalog>>,
 }
 
 impl Emitter for AnnotateSnippetEmitterWriter {
     /// The entry point for the diagnostics generation
     fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let translated = self.translate_diagnostic(diag);
+        let diag = &*translated;
         let mut children = diag.children.clone();
         let (mut primary_span, suggestions) = self.primary_span_formatted(&diag);
 
@@ -54,6 +58,10 @@ impl Emitter for AnnotateSnippetEmitterWriter {
         self.source_map.as_ref()
     }
 
+    fn catalog(&self) -> Option<&Catalog> {
+        self.catalog.as_deref()
+    }
+
     fn should_show_explain(&self) -> bool {
         !self.short_message
     }
@@ -174,7 +182,7 @@ impl AnnotateSnippetEmitterWriter {
         short_message: bool,
         macro_backtrace: bool,
     ) -> Self {
-        Self { source_map, short_message, ui_testing: false, macro_backtrace }
+        Self { source_map, short_message, ui_testing: false, macro_backtrace, catalog: None }
     }
 
     /// Allows to modify `Self` to enable or disable the `ui_testing` flag.
@@ -185,6 +193,11 @@ impl AnnotateSnippetEmitterWriter {
         self
     }
 
+    pub fn with_catalog(mut self, catalog: Option<Lrc<Catalog>>) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
     fn emit_messages_default(
         &mut self,
         level: &Level,
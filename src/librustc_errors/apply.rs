@@ -0,0 +1,192 @@
+//! Applying suggestions to the source code (see `-Z apply-suggestions`).
+//!
+//! Only the suggestions which are machine applicable and have a single
+//! substitution are applied, so that the result is what the compiler would
+//! have done had it been allowed to edit the code. Suggestions inside macro
+//! expansions are left alone, as editing the expansion site could break other
+//! uses of the macro.
+//!
+//! The suggestions are applied all at once when the compilation is finished.
+//! A suggestion overlapping one which was emitted before it is skipped, as
+//! applying both could produce garbage. It may well apply once the compiler is
+//! run again on the fixed code.
+
+use crate::{Applicability, CodeSuggestion, Diagnostic, Level};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{BytePos, FileName, Pos, SourceFile};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// An edit of a file, in byte offsets relative to the file as it was read
+/// (i.e. with normalized line endings).
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Edit {
+    lo: BytePos,
+    hi: BytePos,
+    snippet: String,
+}
+
+impl Edit {
+    fn overlaps(&self, other: &Edit) -> bool {
+        // An insertion overlaps the edits starting at the same position, e.g.
+        // another insertion or a replacement, as their order matters.
+        let is_insertion = |edit: &Edit| edit.lo == edit.hi;
+        (self.lo < other.hi && other.lo < self.hi)
+            || (self.lo == other.lo && (is_insertion(self) || is_insertion(other)))
+    }
+}
+
+/// A suggestion resolved to the edits of the files it changes.
+type Fix = Vec<(PathBuf, Edit)>;
+
+#[derive(Default)]
+pub struct SuggestionApplier {
+    fixes: Vec<Fix>,
+}
+
+impl SuggestionApplier {
+    /// Records the applicable suggestions of a diagnostic.
+    pub fn collect(&mut self, diag: &Diagnostic, sm: &SourceMap) {
+        for suggestion in &diag.suggestions {
+            if let Some(fix) = resolve(suggestion, sm) {
+                // The same suggestion may be emitted several times, e.g. for
+                // each instantiation of a generic function.
+                if !self.fixes.contains(&fix) {
+                    self.fixes.push(fix);
+                }
+            }
+        }
+    }
+
+    /// Applies the recorded suggestions and returns the messages summarizing
+    /// what was changed.
+    pub fn apply(self, sm: &SourceMap) -> Vec<(Level, String)> {
+        // The edits of every file, with the number of suggestions they are from.
+        let mut files: FxHashMap<PathBuf, (usize, Vec<Edit>)> = FxHashMap::default();
+        let mut skipped = 0;
+        for fix in self.fixes {
+            let overlaps = fix.iter().any(|(path, edit)| {
+                files.get(path).map_or(false, |(_, edits)| edits.iter().any(|e| e.overlaps(edit)))
+            });
+            if overlaps {
+                skipped += 1;
+                continue;
+            }
+            let mut paths: Vec<_> = fix.iter().map(|(path, _)| path.clone()).collect();
+            paths.sort();
+            paths.dedup();
+            for path in paths {
+                files.entry(path).or_default().0 += 1;
+            }
+            for (path, edit) in fix {
+                files.entry(path).or_default().1.push(edit);
+            }
+        }
+
+        let mut messages = Vec::new();
+        let mut files: Vec<_> = files.into_iter().collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, (count, edits)) in files {
+            let message = match apply_edits(&path, edits, sm) {
+                Ok(()) => (
+                    Level::Note,
+                    format!(
+                        "applied {} suggestion{} to `{}`",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        path.display()
+                    ),
+                ),
+                Err(e) => (
+                    Level::Warning,
+                    format!("failed to apply suggestions to `{}`: {}", path.display(), e),
+                ),
+            };
+            messages.push(message);
+        }
+        if skipped > 0 {
+            messages.push((
+                Level::Note,
+                format!(
+                    "{} suggestion{} not applied as {} overlapped other suggestions; \
+                     compiling again may apply {}",
+                    skipped,
+                    if skipped == 1 { " was" } else { "s were" },
+                    if skipped == 1 { "it" } else { "they" },
+                    if skipped == 1 { "it" } else { "them" },
+                ),
+            ));
+        }
+        messages
+    }
+}
+
+/// Resolves a suggestion to the edits it makes, if it can be applied.
+fn resolve(suggestion: &CodeSuggestion, sm: &SourceMap) -> Option<Fix> {
+    if suggestion.applicability != Applicability::MachineApplicable
+        || suggestion.substitutions.len() != 1
+    {
+        return None;
+    }
+
+    let mut fix = Vec::new();
+    for part in &suggestion.substitutions[0].parts {
+        if part.span.is_dummy() || part.span.from_expansion() {
+            return None;
+        }
+        let lo = sm.lookup_byte_offset(part.span.lo());
+        let hi = sm.lookup_byte_offset(part.span.hi());
+        // The file is edited where it was read from, even if its name was
+        // remapped with `--remap-path-prefix`.
+        let path = match &lo.sf.unmapped_path {
+            Some(FileName::Real(path)) if lo.sf.start_pos == hi.sf.start_pos => path.clone(),
+            _ => return None,
+        };
+        fix.push((path, Edit { lo: lo.pos, hi: hi.pos, snippet: part.snippet.clone() }));
+    }
+    Some(fix)
+}
+
+/// Applies `edits`, which don't overlap, to the file at `path`.
+fn apply_edits(path: &Path, mut edits: Vec<Edit>, sm: &SourceMap) -> io::Result<()> {
+    let file = sm
+        .files()
+        .iter()
+        .find(|sf| sf.unmapped_path == Some(FileName::Real(path.to_path_buf())))
+        .cloned()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the file was not loaded"))?;
+    let original = fs::read_to_string(path)?;
+    if !is_unchanged(&file, &original) {
+        let msg = "the file was modified during the compilation";
+        return Err(io::Error::new(io::ErrorKind::Other, msg));
+    }
+
+    // Line endings were normalized when the file was read, so the offsets of
+    // the edits have to be mapped back to the original file.
+    let newline = if original.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut fixed = original.clone();
+    edits.sort_by_key(|edit| edit.lo);
+    for edit in edits.iter().rev() {
+        let lo = file.original_relative_byte_pos(file.start_pos + edit.lo).to_usize();
+        let hi = file.original_relative_byte_pos(file.start_pos + edit.hi).to_usize();
+        fixed.replace_range(lo..hi, &edit.snippet.replace('\n', newline));
+    }
+    if fixed != original {
+        fs::write(path, fixed)?;
+    }
+    Ok(())
+}
+
+/// Returns whether `original`, as read from the disk now, is the source code
+/// `file` was created from.
+fn is_unchanged(file: &SourceFile, original: &str) -> bool {
+    let original = if original.starts_with('\u{feff}') { &original[3..] } else { original };
+    let original = original.replace("\r\n", "\n");
+    file.src.as_ref().map_or(false, |src| **src == original)
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::Span;
+use std::env;
+use std::process;
+
+fn with_default_globals(f: impl FnOnce()) {
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || rustc_span::GLOBALS.set(&globals, f))
+}
+
+/// Writes `code` to a file, applies the `suggestions` (spans relative to the
+/// start of the file) and returns the fixed code with the messages.
+fn apply_to(
+    name: &str,
+    code: &str,
+    suggestions: &[(u32, u32, &str, Applicability)],
+) -> (String, Vec<(Level, String)>) {
+    apply_with_mapping(FilePathMapping::empty(), name, code, suggestions)
+}
+
+/// Like `apply_to`, but the paths of the files are mapped with `mapping`.
+fn apply_with_mapping(
+    mapping: FilePathMapping,
+    name: &str,
+    code: &str,
+    suggestions: &[(u32, u32, &str, Applicability)],
+) -> (String, Vec<(Level, String)>) {
+    let path = env::temp_dir().join(format!("rustc-apply-{}-{}.rs", process::id(), name));
+    fs::write(&path, code).unwrap();
+
+    let mut result = None;
+    with_default_globals(|| {
+        let sm = SourceMap::new(mapping);
+        let file = sm.load_file(&path).unwrap();
+
+        let mut applier = SuggestionApplier::default();
+        for &(lo, hi, snippet, applicability) in suggestions {
+            let span =
+                Span::with_root_ctxt(file.start_pos + BytePos(lo), file.start_pos + BytePos(hi));
+            let mut diag = Diagnostic::new(Level::Warning, "foo");
+            diag.span_suggestion(span, "bar", snippet.to_string(), applicability);
+            applier.collect(&diag, &sm);
+        }
+        result = Some(applier.apply(&sm));
+    });
+
+    let fixed = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    (fixed, result.unwrap())
+}
+
+#[test]
+fn machine_applicable() {
+    let (fixed, messages) = apply_to(
+        "machine-applicable",
+        "let mut x = 1;\nlet y = 2;\n",
+        &[
+            (4, 8, "", Applicability::MachineApplicable),
+            (19, 20, "_y", Applicability::MachineApplicable),
+            (0, 3, "const", Applicability::MaybeIncorrect),
+        ],
+    );
+    assert_eq!(fixed, "let x = 1;\nlet _y = 2;\n");
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].0, Level::Note);
+    assert!(messages[0].1.starts_with("applied 2 suggestions to"));
+}
+
+#[test]
+fn overlapping() {
+    let (fixed, messages) = apply_to(
+        "overlapping",
+        "let x = 1;\n",
+        &[
+            (4, 5, "_x", Applicability::MachineApplicable),
+            (4, 5, "_x", Applicability::MachineApplicable),
+            (0, 9, "let y = 2", Applicability::MachineApplicable),
+        ],
+    );
+    assert_eq!(fixed, "let _x = 1;\n");
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].1.starts_with("applied 1 suggestion to"));
+    assert!(messages[1].1.starts_with("1 suggestion was not applied"));
+}
+
+#[test]
+fn insertion_at_start_of_replacement() {
+    let (fixed, messages) = apply_to(
+        "insertion-at-start-of-replacement",
+        "let x = 1;\n",
+        &[
+            (4, 5, "_x", Applicability::MachineApplicable),
+            (4, 4, "mut ", Applicability::MachineApplicable),
+        ],
+    );
+    assert_eq!(fixed, "let _x = 1;\n");
+    assert_eq!(messages.len(), 2);
+    assert!(messages[1].1.starts_with("1 suggestion was not applied"));
+}
+
+#[test]
+fn remapped() {
+    let mapping = FilePathMapping::new(vec![(env::temp_dir(), "/remapped".into())]);
+    let (fixed, messages) = apply_with_mapping(
+        mapping,
+        "remapped",
+        "let x = 1;\n",
+        &[(4, 5, "_x", Applicability::MachineApplicable)],
+    );
+    assert_eq!(fixed, "let _x = 1;\n");
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].1.starts_with("applied 1 suggestion to"));
+}
+
+#[test]
+fn crlf() {
+    let (fixed, _) = apply_to(
+        "crlf",
+        "\u{feff}let x = 1;\r\nlet y = 2;\r\n",
+        &[
+            (15, 16, "_y", Applicability::MachineApplicable),
+            (10, 10, "\nlet z = 3;", Applicability::MachineApplicable),
+        ],
+    );
+    assert_eq!(fixed, "\u{feff}let x = 1;\r\nlet z = 3;\r\nlet _y = 2;\r\n");
+}
@@ -0,0 +1,212 @@
+//! Baselines of known diagnostics.
+//!
+//! A baseline records the warnings and lints of a crate, so that later
+//! compilations can suppress the ones that are already known and only report
+//! the ones that were introduced since (see `-Z diagnostic-baseline` and
+//! `-Z write-diagnostic-baseline`).
+//!
+//! Diagnostics are identified by their code or lint name, the def path of
+//! their item and a fingerprint of their message. Line numbers are left out on
+//! purpose, so that unrelated edits don't change the identity of the
+//! diagnostics, and so is the file, so that moving an item to another file
+//! doesn't either. Diagnostics with the same identity are counted, and only as
+//! many of them are suppressed as the baseline contains. The file stands in
+//! for the item of the diagnostics whose item isn't known (see `locate`).
+//!
+//! A baseline file has a line for each diagnostic, with the fingerprint, the
+//! code, the item and the message separated by tabs. The message is only there
+//! for the readers of the file.
+//!
+//! Suppressed diagnostics are still debt to pay off, so they are listed in a
+//! report next to the baseline file, with `.suppressed` appended to its name.
+//! It has the same format as the baseline, with the location of the
+//! diagnostic in front.
+
+use crate::{locate, Diagnostic, DiagnosticId, Level};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_span::source_map::SourceMap;
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The stable identity of a diagnostic.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+struct DiagnosticKey {
+    fingerprint: u64,
+    /// The error code or lint name, or `-` if there is none.
+    code: String,
+    /// The def path of the item, or the file of the primary span if the item
+    /// isn't known, or `-` if neither is.
+    item: String,
+}
+
+impl DiagnosticKey {
+    fn new(diag: &Diagnostic, sm: Option<&SourceMap>) -> DiagnosticKey {
+        let mut hasher = StableHasher::new();
+        diag.message().hash(&mut hasher);
+        let code = match &diag.code {
+            Some(DiagnosticId::Error(code)) | Some(DiagnosticId::Lint(code)) => code.clone(),
+            None => "-".to_string(),
+        };
+        let item = locate(diag, sm).map_or_else(|| "-".to_string(), |item| item.path);
+        DiagnosticKey { fingerprint: hasher.finish::<u64>(), code, item }
+    }
+
+    /// Returns the line of the key in a baseline file.
+    fn to_line(&self, message: &str) -> String {
+        format!(
+            "{:016x}\t{}\t{}\t{}",
+            self.fingerprint,
+            self.code,
+            self.item,
+            message.replace('\n', " ")
+        )
+    }
+}
+
+pub struct DiagnosticBaseline {
+    /// The file the known diagnostics were read from.
+    input: Option<PathBuf>,
+    /// How many diagnostics of each identity may still be suppressed.
+    known: FxHashMap<DiagnosticKey, usize>,
+    /// The file the diagnostics of this compilation are written to.
+    output: Option<PathBuf>,
+    /// The diagnostics of this compilation, with their message.
+    emitted: Vec<(DiagnosticKey, String)>,
+    /// The lines of the report of the suppressed diagnostics.
+    suppressed: Vec<String>,
+}
+
+impl DiagnosticBaseline {
+    /// Creates a baseline suppressing the diagnostics listed in the file
+    /// `input`, if any, which writes the diagnostics of this compilation to
+    /// the file `output`, if any, once it is finished.
+    pub fn new(input: Option<PathBuf>, output: Option<PathBuf>) -> io::Result<DiagnosticBaseline> {
+        let mut known = FxHashMap::default();
+        if let Some(input) = &input {
+            for (i, line) in fs::read_to_string(input)?.lines().enumerate() {
+                let key = parse_line(line).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("malformed line {}", i + 1))
+                })?;
+                *known.entry(key).or_insert(0) += 1;
+            }
+        }
+        Ok(DiagnosticBaseline { input, known, output, emitted: Vec::new(), suppressed: Vec::new() })
+    }
+
+    /// Records a diagnostic and returns whether it is suppressed.
+    ///
+    /// Only warnings and lints, including denied ones, are recorded: other
+    /// errors can't be ignored.
+    pub fn suppress(&mut self, diag: &Diagnostic, sm: Option<&SourceMap>) -> bool {
+        let is_lint = match diag.code {
+            Some(DiagnosticId::Lint(_)) => true,
+            _ => false,
+        };
+        if diag.level != Level::Warning && !is_lint {
+            return false;
+        }
+
+        let key = DiagnosticKey::new(diag, sm);
+        let message = diag.message();
+        let suppressed = match self.known.get_mut(&key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        };
+        if suppressed {
+            let location = match (sm, diag.span.primary_span()) {
+                (Some(sm), Some(span)) if !span.is_dummy() => sm.span_to_string(span),
+                _ => "-".to_string(),
+            };
+            self.suppressed.push(format!("{}\t{}", location, key.to_line(&message)));
+        }
+        self.emitted.push((key, message));
+        suppressed
+    }
+
+    /// Writes the report of the suppressed diagnostics and the baseline file,
+    /// if requested, and returns the messages summarizing how the diagnostics
+    /// of this compilation compare to the baseline.
+    pub fn finish(mut self) -> Vec<(Level, String)> {
+        let mut messages = Vec::new();
+        if let Some(input) = &self.input {
+            // The report is written even if it's empty, so that it's never
+            // left over from an earlier compilation.
+            let report = report_path(input);
+            if let Err(e) = write_lines(&report, &self.suppressed) {
+                let message = format!("failed to write the report `{}`: {}", report.display(), e);
+                messages.push((Level::Warning, message));
+            } else if !self.suppressed.is_empty() {
+                let message = format!(
+                    "{} known diagnostic{} suppressed by the baseline `{}`, see `{}`",
+                    self.suppressed.len(),
+                    if self.suppressed.len() == 1 { " was" } else { "s were" },
+                    input.display(),
+                    report.display()
+                );
+                messages.push((Level::Note, message));
+            }
+            let fixed: usize = self.known.values().sum();
+            if fixed > 0 {
+                let message = format!(
+                    "{} diagnostic{} of the baseline `{}` no longer occur{}",
+                    fixed,
+                    if fixed == 1 { "" } else { "s" },
+                    input.display(),
+                    if fixed == 1 { "s" } else { "" }
+                );
+                messages.push((Level::Note, message));
+            }
+        }
+
+        if let Some(output) = &self.output {
+            if let Err(e) = write_baseline(output, &mut self.emitted) {
+                let message = format!("failed to write the baseline `{}`: {}", output.display(), e);
+                messages.push((Level::Warning, message));
+            }
+        }
+        messages
+    }
+}
+
+fn parse_line(line: &str) -> Option<DiagnosticKey> {
+    let mut fields = line.splitn(4, '\t');
+    let fingerprint = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let code = fields.next()?.to_string();
+    let item = fields.next()?.to_string();
+    Some(DiagnosticKey { fingerprint, code, item })
+}
+
+/// Returns the path of the report of the diagnostics suppressed by the
+/// baseline `input`.
+fn report_path(input: &Path) -> PathBuf {
+    let mut name = input.file_name().map_or_else(OsString::new, |name| name.to_owned());
+    name.push(".suppressed");
+    input.with_file_name(name)
+}
+
+fn write_baseline(path: &Path, emitted: &mut Vec<(DiagnosticKey, String)>) -> io::Result<()> {
+    // Sorted, so that the file only changes when the diagnostics do.
+    emitted.sort();
+    let lines: Vec<_> = emitted.iter().map(|(key, message)| key.to_line(message)).collect();
+    write_lines(path, &lines)
+}
+
+fn write_lines(path: &Path, lines: &[String]) -> io::Result<()> {
+    let mut contents = String::new();
+    for line in lines {
+        contents.push_str(line);
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
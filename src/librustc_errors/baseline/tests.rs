@@ -0,0 +1,141 @@
+use super::*;
+use crate::DiagnosticItem;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+use std::env;
+use std::process;
+
+fn with_default_globals(f: impl FnOnce()) {
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || rustc_span::GLOBALS.set(&globals, f))
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("rustc-baseline-{}-{}", process::id(), name))
+}
+
+fn lint(level: Level, name: &str, message: &str, lo: u32) -> Diagnostic {
+    let mut diag = Diagnostic::new(level, message);
+    diag.code(DiagnosticId::Lint(name.to_string()));
+    diag.set_span(Span::with_root_ctxt(BytePos(lo), BytePos(lo + 1)));
+    diag
+}
+
+#[test]
+fn round_trip() {
+    with_default_globals(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), "let x = 1;\n".repeat(4));
+        let path = temp_file("round-trip");
+
+        let mut baseline = DiagnosticBaseline::new(None, Some(path.clone())).unwrap();
+        for &lo in &[4, 15] {
+            let diag = lint(Level::Warning, "unused_variables", "unused variable: `x`", lo);
+            assert!(!baseline.suppress(&diag, Some(&sm)));
+        }
+        assert!(baseline.finish().is_empty());
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        assert!(contents.contains("\tunused_variables\ttest.rs\tunused variable: `x`\n"));
+
+        // The diagnostics are identified regardless of their lines, but only
+        // as many are suppressed as the baseline contains.
+        let mut baseline = DiagnosticBaseline::new(Some(path.clone()), None).unwrap();
+        for &lo in &[26, 37] {
+            let diag = lint(Level::Warning, "unused_variables", "unused variable: `x`", lo);
+            assert!(baseline.suppress(&diag, Some(&sm)));
+        }
+        let diag = lint(Level::Warning, "unused_variables", "unused variable: `x`", 4);
+        assert!(!baseline.suppress(&diag, Some(&sm)));
+        let diag = lint(Level::Warning, "unused_variables", "unused variable: `y`", 4);
+        assert!(!baseline.suppress(&diag, Some(&sm)));
+
+        let messages = baseline.finish();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, Level::Note);
+        assert!(messages[0].1.starts_with("2 known diagnostics were suppressed"));
+
+        // The suppressed diagnostics are listed in the report.
+        let report = fs::read_to_string(report_path(&path)).unwrap();
+        assert_eq!(report.lines().count(), 2);
+        assert!(report.starts_with("test.rs:3:5: 3:6\t"));
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(report_path(&path)).unwrap();
+    });
+}
+
+#[test]
+fn identified_by_item() {
+    with_default_globals(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("a.rs").to_owned().into(), "let x = 1;\n".to_string());
+        sm.new_source_file(Path::new("b.rs").to_owned().into(), "let x = 1;\n".to_string());
+        let path = temp_file("identified-by-item");
+        let item = DiagnosticItem { path: "foo::bar".to_string(), module: "foo".to_string() };
+
+        let mut baseline = DiagnosticBaseline::new(None, Some(path.clone())).unwrap();
+        let mut diag = lint(Level::Warning, "unused_variables", "unused variable: `x`", 4);
+        diag.set_item(item.clone());
+        assert!(!baseline.suppress(&diag, Some(&sm)));
+        assert!(baseline.finish().is_empty());
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\tunused_variables\tfoo::bar\tunused variable: `x`\n"));
+
+        // The item was moved to another file.
+        let mut baseline = DiagnosticBaseline::new(Some(path.clone()), None).unwrap();
+        let mut diag = lint(Level::Warning, "unused_variables", "unused variable: `x`", 15);
+        diag.set_item(item);
+        assert!(baseline.suppress(&diag, Some(&sm)));
+        assert_eq!(baseline.finish().len(), 1);
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(report_path(&path)).unwrap();
+    });
+}
+
+#[test]
+fn stale_entries() {
+    let path = temp_file("stale-entries");
+    fs::write(&path, "00000000000000ff\tdead_code\tsrc/lib.rs\tfunction is never used\n").unwrap();
+
+    let baseline = DiagnosticBaseline::new(Some(path.clone()), None).unwrap();
+    let messages = baseline.finish();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].1.starts_with("1 diagnostic of the baseline"));
+    assert!(messages[0].1.ends_with("no longer occurs"));
+    // Nothing was suppressed, but the report is still written.
+    assert_eq!(fs::read_to_string(report_path(&path)).unwrap(), "");
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(report_path(&path)).unwrap();
+}
+
+#[test]
+fn errors_are_not_suppressed() {
+    let path = temp_file("errors");
+    let mut baseline = DiagnosticBaseline::new(None, Some(path.clone())).unwrap();
+    let mut diag = Diagnostic::new(Level::Error, "mismatched types");
+    diag.code(DiagnosticId::Error("E0308".to_string()));
+    assert!(!baseline.suppress(&diag, None));
+    // Denied lints are errors too, but they are recorded all the same.
+    assert!(!baseline.suppress(&lint(Level::Error, "dead_code", "unused", 0), None));
+    assert!(baseline.finish().is_empty());
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert_eq!(contents.lines().count(), 1);
+    assert!(contents.contains("\tdead_code\t-\tunused\n"));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn malformed() {
+    let path = temp_file("malformed");
+    fs::write(&path, "not a baseline\n").unwrap();
+    let err = DiagnosticBaseline::new(Some(path.clone()), None).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,136 @@
+//! Limits on the number of errors shown (see `-Z max-errors` and
+//! `-Z max-errors-per-code`), and the summary of the errors of a compilation
+//! (see `-Z error-summary`).
+//!
+//! A single mistake, like a wrong type in a generic helper, can cause hundreds
+//! of errors which scroll the root cause away. Once a limit is reached, the
+//! following errors are still counted, so that the compilation fails all the
+//! same, but they are not shown. They are summarized by code and module instead
+//! when the compilation is finished, like "and 143 more E0308 errors in
+//! `foo::bar`".
+//!
+//! Only errors, including denied lints, are limited: warnings are always
+//! shown, and so are bugs and fatal errors, which end the compilation.
+//!
+//! The errors reported before the crate is lowered, like the ones of the
+//! parser and of name resolution, are grouped by file (see `locate`).
+
+use crate::{locate, Diagnostic, DiagnosticId, HandlerFlags, Level};
+
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_span::source_map::SourceMap;
+
+#[cfg(test)]
+mod tests;
+
+/// The code and module of an error, for grouping.
+type ErrorKey = (Option<String>, Option<String>);
+
+#[derive(Default)]
+pub struct ErrorBudget {
+    /// The number of errors shown in total, if limited.
+    max_errors: Option<usize>,
+    /// The number of errors shown for each code, if limited.
+    max_errors_per_code: Option<usize>,
+    /// Whether to count the errors by code and module for the summary.
+    summary: bool,
+    shown: usize,
+    /// The number of errors with each code, shown or not.
+    per_code: FxHashMap<String, usize>,
+    /// The number of errors which were not shown, in the order they occurred.
+    hidden: FxIndexMap<ErrorKey, usize>,
+    /// The number of errors of each code and module, for the summary.
+    by_code: FxHashMap<Option<String>, usize>,
+    by_module: FxHashMap<Option<String>, usize>,
+}
+
+impl ErrorBudget {
+    /// Creates the budget requested by `flags`, if any.
+    pub fn new(flags: &HandlerFlags) -> Option<ErrorBudget> {
+        if flags.max_errors.is_none() && flags.max_errors_per_code.is_none() && !flags.error_summary
+        {
+            return None;
+        }
+        Some(ErrorBudget {
+            max_errors: flags.max_errors,
+            max_errors_per_code: flags.max_errors_per_code,
+            summary: flags.error_summary,
+            ..Default::default()
+        })
+    }
+
+    /// Records a diagnostic and returns whether it should be shown.
+    pub fn admit(&mut self, diag: &Diagnostic, sm: Option<&SourceMap>) -> bool {
+        if diag.level != Level::Error {
+            return true;
+        }
+
+        let code = match &diag.code {
+            Some(DiagnosticId::Error(code)) | Some(DiagnosticId::Lint(code)) => Some(code.clone()),
+            None => None,
+        };
+        let module = locate(diag, sm).map(|item| item.module);
+        if self.summary {
+            *self.by_code.entry(code.clone()).or_insert(0) += 1;
+            *self.by_module.entry(module.clone()).or_insert(0) += 1;
+        }
+
+        let code_count = code.as_ref().map(|code| {
+            let count = self.per_code.entry(code.clone()).or_insert(0);
+            *count += 1;
+            *count
+        });
+        let over_total = self.max_errors.map_or(false, |max| self.shown >= max);
+        let over_code = match (self.max_errors_per_code, code_count) {
+            (Some(max), Some(count)) => count > max,
+            _ => false,
+        };
+        if over_total || over_code {
+            *self.hidden.entry((code, module)).or_insert(0) += 1;
+            return false;
+        }
+        self.shown += 1;
+        true
+    }
+
+    /// Returns the messages summarizing the errors which were not shown and,
+    /// if requested, the tables of the errors by code and by module.
+    pub fn finish(self) -> Vec<(Level, String)> {
+        let mut messages = Vec::new();
+        for ((code, module), count) in self.hidden {
+            let mut message = format!("and {} more ", count);
+            if let Some(code) = code {
+                message.push_str(&code);
+                message.push(' ');
+            }
+            message.push_str(if count == 1 { "error" } else { "errors" });
+            if let Some(module) = module {
+                message.push_str(&format!(" in `{}`", module));
+            }
+            messages.push((Level::Note, message));
+        }
+
+        if self.summary && !self.by_code.is_empty() {
+            let mut by_code = String::from("errors by code:");
+            push_rows(&mut by_code, self.by_code, "without a code");
+            messages.push((Level::Note, by_code));
+            let mut by_module = String::from("errors by module:");
+            push_rows(&mut by_module, self.by_module, "without a location");
+            messages.push((Level::Note, by_module));
+        }
+        messages
+    }
+}
+
+/// Appends a row for each group to `table`, with the most frequent first.
+fn push_rows(table: &mut String, counts: FxHashMap<Option<String>, usize>, other: &str) {
+    let mut rows: Vec<_> = counts.into_iter().collect();
+    rows.sort_by(|(name1, count1), (name2, count2)| {
+        count2.cmp(count1).then_with(|| name1.cmp(name2))
+    });
+    let width = rows.iter().map(|(_, count)| count.to_string().len()).max().unwrap_or(0);
+    for (name, count) in rows {
+        let name = name.as_ref().map_or(other, |name| name);
+        table.push_str(&format!("\n  {:>width$}  {}", count, name, width = width));
+    }
+}
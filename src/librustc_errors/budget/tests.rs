@@ -0,0 +1,117 @@
+use super::*;
+use crate::DiagnosticItem;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+use std::path::Path;
+
+fn with_default_globals(f: impl FnOnce()) {
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || rustc_span::GLOBALS.set(&globals, f))
+}
+
+fn error(code: &str, lo: u32) -> Diagnostic {
+    let mut diag = Diagnostic::new(Level::Error, "mismatched types");
+    diag.code(DiagnosticId::Error(code.to_string()));
+    diag.set_span(Span::with_root_ctxt(BytePos(lo), BytePos(lo + 1)));
+    diag
+}
+
+fn source_map() -> SourceMap {
+    let sm = SourceMap::new(FilePathMapping::empty());
+    sm.new_source_file(Path::new("foo.rs").to_owned().into(), "let x = 1;\n".repeat(4));
+    sm.new_source_file(Path::new("bar.rs").to_owned().into(), "let x = 1;\n".repeat(4));
+    sm
+}
+
+#[test]
+fn no_budget() {
+    assert!(ErrorBudget::new(&HandlerFlags::default()).is_none());
+}
+
+#[test]
+fn max_errors_per_code() {
+    with_default_globals(|| {
+        let sm = source_map();
+        let flags = HandlerFlags { max_errors_per_code: Some(2), ..Default::default() };
+        let mut budget = ErrorBudget::new(&flags).unwrap();
+
+        let shown: Vec<_> = [0, 11, 22, 33, 50]
+            .iter()
+            .map(|&lo| budget.admit(&error("E0308", lo), Some(&sm)))
+            .collect();
+        assert_eq!(shown, [true, true, false, false, false]);
+        assert!(budget.admit(&error("E0599", 0), Some(&sm)));
+        // Warnings are always shown.
+        assert!(budget.admit(&Diagnostic::new(Level::Warning, "unused variable"), Some(&sm)));
+
+        let messages = budget.finish();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0], (Level::Note, "and 2 more E0308 errors in `foo.rs`".to_string()));
+        assert_eq!(messages[1], (Level::Note, "and 1 more E0308 error in `bar.rs`".to_string()));
+    });
+}
+
+#[test]
+fn max_errors() {
+    let flags = HandlerFlags { max_errors: Some(1), ..Default::default() };
+    let mut budget = ErrorBudget::new(&flags).unwrap();
+
+    assert!(budget.admit(&Diagnostic::new(Level::Error, "first"), None));
+    assert!(!budget.admit(&Diagnostic::new(Level::Error, "second"), None));
+    assert!(!budget.admit(&Diagnostic::new(Level::Error, "third"), None));
+    // Bugs and fatal errors are always shown.
+    assert!(budget.admit(&Diagnostic::new(Level::Bug, "bug"), None));
+    assert!(budget.admit(&Diagnostic::new(Level::Fatal, "fatal"), None));
+
+    let messages = budget.finish();
+    assert_eq!(messages, [(Level::Note, "and 2 more errors".to_string())]);
+}
+
+#[test]
+fn summary() {
+    with_default_globals(|| {
+        let sm = source_map();
+        let flags = HandlerFlags { error_summary: true, ..Default::default() };
+        let mut budget = ErrorBudget::new(&flags).unwrap();
+
+        for &lo in &[0, 11, 22, 50] {
+            assert!(budget.admit(&error("E0308", lo), Some(&sm)));
+        }
+        assert!(budget.admit(&error("E0599", 55), Some(&sm)));
+        assert!(budget.admit(&Diagnostic::new(Level::Error, "no location"), Some(&sm)));
+
+        let messages = budget.finish();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, Level::Note);
+        assert_eq!(messages[0].1, "errors by code:\n  4  E0308\n  1  without a code\n  1  E0599");
+        assert_eq!(messages[1].0, Level::Note);
+        assert_eq!(
+            messages[1].1,
+            "errors by module:\n  3  foo.rs\n  2  bar.rs\n  1  without a location"
+        );
+    });
+}
+
+#[test]
+fn grouped_by_module() {
+    with_default_globals(|| {
+        let sm = source_map();
+        let flags = HandlerFlags { max_errors: Some(1), error_summary: true, ..Default::default() };
+        let mut budget = ErrorBudget::new(&flags).unwrap();
+
+        for (lo, path) in [(0, "foo::a"), (11, "foo::b"), (50, "foo::c")].iter() {
+            let mut diag = error("E0308", *lo);
+            diag.set_item(DiagnosticItem { path: path.to_string(), module: "foo".to_string() });
+            budget.admit(&diag, Some(&sm));
+        }
+        // Without an item, the file stands in for the module.
+        assert!(!budget.admit(&error("E0308", 22), Some(&sm)));
+
+        let messages = budget.finish();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].1, "and 2 more E0308 errors in `foo`");
+        assert_eq!(messages[1].1, "and 1 more E0308 error in `foo.rs`");
+        assert_eq!(messages[3].1, "errors by module:\n  3  foo\n  1  foo.rs");
+    });
+}
@@ -1,4 +1,5 @@
 use crate::snippet::Style;
+use crate::translation::MessageId;
 use crate::Applicability;
 use crate::CodeSuggestion;
 use crate::Level;
@@ -22,6 +23,27 @@ pub struct Diagnostic {
     /// as a sort key to sort a buffer of diagnostics.  By default, it is the primary span of
     /// `span` if there is one.  Otherwise, it is `DUMMY_SP`.
     pub sort_span: Span,
+
+    /// The identifier of `message` in the translation catalogs, if any.
+    pub message_id: Option<MessageId>,
+    /// The identifiers of the labels of `span` in the translation catalogs,
+    /// with the span and the text of the label they translate.
+    pub label_ids: Vec<(Span, String, MessageId)>,
+
+    /// The item the diagnostic is about, if it is known where the diagnostic
+    /// is created. Otherwise, it is looked up by the primary span when needed
+    /// (see `SPAN_ITEM`).
+    pub item: Option<DiagnosticItem>,
+}
+
+/// The item a diagnostic is about, which tells diagnostics apart across
+/// compilations better than their lines do.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub struct DiagnosticItem {
+    /// The def path of the item, like `foo::{{impl}}::bar`.
+    pub path: String,
+    /// The def path of the module of the item, or `crate` for the crate root.
+    pub module: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
@@ -37,6 +59,8 @@ pub struct SubDiagnostic {
     pub message: Vec<(String, Style)>,
     pub span: MultiSpan,
     pub render_span: Option<MultiSpan>,
+    /// The identifier of `message` in the translation catalogs, if any.
+    pub message_id: Option<MessageId>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -100,6 +124,9 @@ impl Diagnostic {
             children: vec![],
             suggestions: vec![],
             sort_span: DUMMY_SP,
+            message_id: None,
+            label_ids: vec![],
+            item: None,
         }
     }
 
@@ -479,6 +506,12 @@ impl Diagnostic {
         self
     }
 
+    /// Sets the item the diagnostic is about.
+    pub fn set_item(&mut self, item: DiagnosticItem) -> &mut Self {
+        self.item = Some(item);
+        self
+    }
+
     pub fn clear_code(&mut self) -> &mut Self {
         self.code = None;
         self
@@ -490,6 +523,8 @@ impl Diagnostic {
 
     pub fn set_primary_message<M: Into<String>>(&mut self, msg: M) -> &mut Self {
         self.message[0] = (msg.into(), Style::NoStyle);
+        // The identifier was the one of the replaced message.
+        self.message_id = None;
         self
     }
 
@@ -501,6 +536,43 @@ impl Diagnostic {
         &self.message
     }
 
+    /// Sets the identifier of the primary message in the translation catalogs.
+    pub fn set_message_id(&mut self, id: MessageId) -> &mut Self {
+        self.message_id = Some(id);
+        self
+    }
+
+    /// Like `span_label`, but the label can be translated with the identifier `id`.
+    pub fn span_label_with_id<T: Into<String>>(
+        &mut self,
+        span: Span,
+        label: T,
+        id: MessageId,
+    ) -> &mut Self {
+        let label = label.into();
+        self.label_ids.push((span, label.clone(), id));
+        self.span_label(span, label)
+    }
+
+    /// Like `note`, but the note can be translated with the identifier `id`.
+    pub fn note_with_id(&mut self, msg: &str, id: MessageId) -> &mut Self {
+        self.note(msg);
+        self.set_last_child_id(id)
+    }
+
+    /// Like `help`, but the help can be translated with the identifier `id`.
+    pub fn help_with_id(&mut self, msg: &str, id: MessageId) -> &mut Self {
+        self.help(msg);
+        self.set_last_child_id(id)
+    }
+
+    fn set_last_child_id(&mut self, id: MessageId) -> &mut Self {
+        if let Some(child) = self.children.last_mut() {
+            child.message_id = Some(id);
+        }
+        self
+    }
+
     /// Used by a lint. Copies over all details *but* the "main
     /// message".
     pub fn copy_details_not_message(&mut self, from: &Diagnostic) {
@@ -523,6 +595,7 @@ impl Diagnostic {
             message: vec![(message.to_owned(), Style::NoStyle)],
             span,
             render_span,
+            message_id: None,
         };
         self.children.push(sub);
     }
@@ -536,7 +609,7 @@ impl Diagnostic {
         span: MultiSpan,
         render_span: Option<MultiSpan>,
     ) {
-        let sub = SubDiagnostic { level, message, span, render_span };
+        let sub = SubDiagnostic { level, message, span, render_span, message_id: None };
         self.children.push(sub);
     }
 }
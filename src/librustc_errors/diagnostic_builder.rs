@@ -1,5 +1,5 @@
 use crate::{Applicability, Handler, Level, StashKey};
-use crate::{Diagnostic, DiagnosticId, DiagnosticStyledString};
+use crate::{Diagnostic, DiagnosticId, DiagnosticItem, DiagnosticStyledString, MessageId};
 
 use log::debug;
 use rustc_span::{MultiSpan, Span};
@@ -191,6 +191,17 @@ impl<'a> DiagnosticBuilder<'a> {
         self
     }
 
+    /// Like `span_label`, but the label can be translated with the identifier `id`.
+    pub fn span_label_with_id<T: Into<String>>(
+        &mut self,
+        span: Span,
+        label: T,
+        id: MessageId,
+    ) -> &mut Self {
+        self.0.diagnostic.span_label_with_id(span, label, id);
+        self
+    }
+
     forward!(pub fn note_expected_found(
         &mut self,
         expected_label: &dyn fmt::Display,
@@ -328,6 +339,10 @@ impl<'a> DiagnosticBuilder<'a> {
 
     forward!(pub fn set_span<S: Into<MultiSpan>>(&mut self, sp: S) -> &mut Self);
     forward!(pub fn code(&mut self, s: DiagnosticId) -> &mut Self);
+    forward!(pub fn set_item(&mut self, item: DiagnosticItem) -> &mut Self);
+    forward!(pub fn set_message_id(&mut self, id: MessageId) -> &mut Self);
+    forward!(pub fn note_with_id(&mut self, msg: &str, id: MessageId) -> &mut Self);
+    forward!(pub fn help_with_id(&mut self, msg: &str, id: MessageId) -> &mut Self);
 
     pub fn allow_suggestions(&mut self, allow: bool) -> &mut Self {
         self.0.allow_suggestions = allow;
@@ -10,10 +10,11 @@
 use Destination::*;
 
 use rustc_span::source_map::SourceMap;
-use rustc_span::{MultiSpan, SourceFile, Span};
+use rustc_span::{FileName, MultiSpan, SourceFile, Span};
 
 use crate::snippet::{Annotation, AnnotationType, Line, MultilineAnnotation, Style, StyledString};
 use crate::styled_buffer::StyledBuffer;
+use crate::translation::Catalog;
 use crate::{
     pluralize, CodeSuggestion, Diagnostic, DiagnosticId, Level, SubDiagnostic, SuggestionStyle,
 };
@@ -24,13 +25,17 @@ use rustc_data_structures::sync::Lrc;
 use rustc_span::hygiene::{ExpnKind, MacroKind};
 use std::borrow::Cow;
 use std::cmp::{max, min, Reverse};
+use std::env;
 use std::io;
 use std::io::prelude::*;
 use std::iter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use termcolor::{Ansi, BufferWriter, ColorChoice, ColorSpec, StandardStream};
 use termcolor::{Buffer, Color, WriteColor};
 
+#[cfg(test)]
+mod tests;
+
 /// Describes the way the content of the `rendered` field of the json output is generated
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HumanReadableErrorType {
@@ -193,8 +198,27 @@ pub trait Emitter {
         true
     }
 
+    /// Formats an error code for the messages about "rustc --explain".
+    fn format_error_code(&self, code: &str) -> String {
+        code.to_string()
+    }
+
     fn source_map(&self) -> Option<&Lrc<SourceMap>>;
 
+    /// The catalog the messages are translated with, if any.
+    fn catalog(&self) -> Option<&Catalog> {
+        None
+    }
+
+    /// Translates the messages of `diag` which have an identifier with the
+    /// catalog of the emitter.
+    fn translate_diagnostic<'a>(&self, diag: &'a Diagnostic) -> Cow<'a, Diagnostic> {
+        match self.catalog() {
+            Some(catalog) => catalog.translate_diagnostic(diag),
+            None => Cow::Borrowed(diag),
+        }
+    }
+
     /// Formats the substitutions of the primary_span
     ///
     /// The are a lot of conditions to this method, but in short:
@@ -322,6 +346,7 @@ pub trait Emitter {
                     message: vec![(msg, Style::NoStyle)],
                     span: MultiSpan::new(),
                     render_span: None,
+                    message_id: None,
                 });
             }
         }
@@ -469,7 +494,13 @@ impl Emitter for EmitterWriter {
         self.sm.as_ref()
     }
 
+    fn catalog(&self) -> Option<&Catalog> {
+        self.catalog.as_deref()
+    }
+
     fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let translated = self.translate_diagnostic(diag);
+        let diag = &*translated;
         let mut children = diag.children.clone();
         let (mut primary_span, suggestions) = self.primary_span_formatted(&diag);
 
@@ -494,6 +525,10 @@ impl Emitter for EmitterWriter {
     fn should_show_explain(&self) -> bool {
         !self.short_message
     }
+
+    fn format_error_code(&self, code: &str) -> String {
+        self.error_code_hyperlink(code)
+    }
 }
 
 /// An emitter that does nothing when emitting a diagnostic.
@@ -558,6 +593,60 @@ pub struct EmitterWriter {
     terminal_width: Option<usize>,
 
     macro_backtrace: bool,
+    catalog: Option<Lrc<Catalog>>,
+    hyperlinks: Option<HyperlinkTemplate>,
+}
+
+/// The URI template of the terminal hyperlinks the locations of the
+/// diagnostics are wrapped in (see `-Z terminal-hyperlinks`).
+#[derive(Clone, Debug)]
+pub struct HyperlinkTemplate(String);
+
+impl HyperlinkTemplate {
+    /// Parses `file`, `vscode`, or a custom template in which `{path}`,
+    /// `{line}` and `{col}` are replaced by the location.
+    pub fn parse(template: &str) -> Result<HyperlinkTemplate, String> {
+        let template = match template {
+            "file" => "file://{path}",
+            "vscode" => "vscode://file{path}:{line}:{col}",
+            _ if template.contains("{path}") => template,
+            _ => return Err(format!("the hyperlink template `{}` has no `{{path}}`", template)),
+        };
+        Ok(HyperlinkTemplate(template.to_string()))
+    }
+
+    fn uri(&self, path: &Path, line: usize, col: usize) -> String {
+        self.0
+            .replace("{path}", &encode_uri_path(path))
+            .replace("{line}", &line.to_string())
+            .replace("{col}", &col.to_string())
+    }
+}
+
+/// Percent-encodes an absolute path, with forward slashes and a leading one
+/// (before the drive letter on Windows), as URIs expect.
+fn encode_uri_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) { path.replace('\\', "/") } else { path.into_owned() };
+    let mut uri = String::with_capacity(path.len() + 1);
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Wraps `text` in an OSC 8 hyperlink to `uri`. Terminals which support them
+/// make the text clickable, the others are expected to ignore the sequences.
+fn hyperlink(text: &str, uri: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", uri, text)
 }
 
 #[derive(Debug)]
@@ -585,6 +674,8 @@ impl EmitterWriter {
             ui_testing: false,
             terminal_width,
             macro_backtrace,
+            catalog: None,
+            hyperlinks: None,
         }
     }
 
@@ -605,6 +696,8 @@ impl EmitterWriter {
             ui_testing: false,
             terminal_width,
             macro_backtrace,
+            catalog: None,
+            hyperlinks: None,
         }
     }
 
@@ -613,6 +706,56 @@ impl EmitterWriter {
         self
     }
 
+    pub fn with_catalog(mut self, catalog: Option<Lrc<Catalog>>) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// Wraps the locations and the error codes in terminal hyperlinks. They
+    /// are only emitted when writing to a terminal, as the escape sequences
+    /// would end up in logs and files otherwise.
+    pub fn with_hyperlinks(mut self, hyperlinks: Option<HyperlinkTemplate>) -> Self {
+        let is_terminal = match self.dst {
+            Terminal(_) | Buffered(_) => atty::is(atty::Stream::Stderr),
+            Raw(..) => false,
+        };
+        self.hyperlinks = hyperlinks.filter(|_| is_terminal);
+        self
+    }
+
+    /// Links the location `text` to the line and column of `file`.
+    fn location_hyperlink(
+        &self,
+        text: String,
+        file: &SourceFile,
+        line: usize,
+        col: usize,
+    ) -> String {
+        let template = match &self.hyperlinks {
+            Some(template) => template,
+            None => return text,
+        };
+        let path = match file.unmapped_path.as_ref().unwrap_or(&file.name) {
+            FileName::Real(path) => path,
+            _ => return text,
+        };
+        let path: PathBuf = match env::current_dir() {
+            Ok(dir) => dir.join(path),
+            Err(_) => return text,
+        };
+        hyperlink(&text, &template.uri(&path, line, col))
+    }
+
+    /// Links an error code to its explanation in the error index.
+    fn error_code_hyperlink(&self, code: &str) -> String {
+        match self.hyperlinks {
+            Some(_) => {
+                hyperlink(code, &format!("https://doc.rust-lang.org/error-index.html#{}", code))
+            }
+            None => code.to_string(),
+        }
+    }
+
     fn maybe_anonymized(&self, line_num: usize) -> String {
         if self.ui_testing { ANONYMIZED_LINE_NUM.to_string() } else { line_num.to_string() }
     }
@@ -1239,7 +1382,7 @@ impl EmitterWriter {
             // only render error codes, not lint codes
             if let Some(DiagnosticId::Error(ref code)) = *code {
                 buffer.append(0, "[", Style::Level(*level));
-                buffer.append(0, &code, Style::Level(*level));
+                buffer.append(0, &self.error_code_hyperlink(code), Style::Level(*level));
                 buffer.append(0, "]", Style::Level(*level));
             }
             if *level != Level::FailureNote && !level_str.is_empty() {
@@ -1285,35 +1428,20 @@ impl EmitterWriter {
             let is_primary = primary_lo.file.name == annotated_file.file.name;
             if is_primary {
                 let loc = primary_lo.clone();
+                let line = sm.doctest_offset_line(&loc.file.name, loc.line);
+                let location = format!("{}:{}:{}", loc.file.name, line, loc.col.0 + 1);
+                let location = self.location_hyperlink(location, &loc.file, line, loc.col.0 + 1);
                 if !self.short_message {
                     // remember where we are in the output buffer for easy reference
                     let buffer_msg_line_offset = buffer.num_lines();
 
                     buffer.prepend(buffer_msg_line_offset, "--> ", Style::LineNumber);
-                    buffer.append(
-                        buffer_msg_line_offset,
-                        &format!(
-                            "{}:{}:{}",
-                            loc.file.name,
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
-                        Style::LineAndColumn,
-                    );
+                    buffer.append(buffer_msg_line_offset, &location, Style::LineAndColumn);
                     for _ in 0..max_line_num_len {
                         buffer.prepend(buffer_msg_line_offset, " ", Style::NoStyle);
                     }
                 } else {
-                    buffer.prepend(
-                        0,
-                        &format!(
-                            "{}:{}:{}: ",
-                            loc.file.name,
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
-                        Style::LineAndColumn,
-                    );
+                    buffer.prepend(0, &format!("{}: ", location), Style::LineAndColumn);
                 }
             } else if !self.short_message {
                 // remember where we are in the output buffer for easy reference
@@ -1325,17 +1453,17 @@ impl EmitterWriter {
                 // Then, the secondary file indicator
                 buffer.prepend(buffer_msg_line_offset + 1, "::: ", Style::LineNumber);
                 let loc = if let Some(first_line) = annotated_file.lines.first() {
-                    let col = if let Some(first_annotation) = first_line.annotations.first() {
-                        format!(":{}", first_annotation.start_col + 1)
-                    } else {
-                        String::new()
+                    let line =
+                        sm.doctest_offset_line(&annotated_file.file.name, first_line.line_index);
+                    let (col, col_num) = match first_line.annotations.first() {
+                        Some(first_annotation) => (
+                            format!(":{}", first_annotation.start_col + 1),
+                            first_annotation.start_col + 1,
+                        ),
+                        None => (String::new(), 1),
                     };
-                    format!(
-                        "{}:{}{}",
-                        annotated_file.file.name,
-                        sm.doctest_offset_line(&annotated_file.file.name, first_line.line_index),
-                        col
-                    )
+                    let loc = format!("{}:{}{}", annotated_file.file.name, line, col);
+                    self.location_hyperlink(loc, &annotated_file.file, line, col_num)
                 } else {
                     annotated_file.file.name.to_string()
                 };
@@ -0,0 +1,81 @@
+use super::*;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn hyperlink_templates() {
+    let path =
+        if cfg!(windows) { Path::new(r"C:\src\my lib.rs") } else { Path::new("/src/my lib.rs") };
+    let file = if cfg!(windows) { "/C:/src/my%20lib.rs" } else { "/src/my%20lib.rs" };
+
+    let template = HyperlinkTemplate::parse("file").unwrap();
+    assert_eq!(template.uri(path, 3, 7), format!("file://{}", file));
+    let template = HyperlinkTemplate::parse("vscode").unwrap();
+    assert_eq!(template.uri(path, 3, 7), format!("vscode://file{}:3:7", file));
+    let template = HyperlinkTemplate::parse("editor://open?file={path}&line={line}").unwrap();
+    assert_eq!(template.uri(path, 3, 7), format!("editor://open?file={}&line=3", file));
+
+    assert!(HyperlinkTemplate::parse("editor://open").is_err());
+}
+
+#[test]
+fn hyperlinks_are_not_written_to_buffers() {
+    let emitter = EmitterWriter::new(Box::new(io::sink()), None, false, false, false, None, false)
+        .with_hyperlinks(Some(HyperlinkTemplate::parse("file").unwrap()));
+    assert_eq!(emitter.error_code_hyperlink("E0308"), "E0308");
+}
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+#[test]
+fn hyperlinks_are_written_to_terminals() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let main = sm.new_source_file(
+            Path::new("main.rs").to_owned().into(),
+            "fn main() { foo(); }\n".to_string(),
+        );
+        let lib =
+            sm.new_source_file(Path::new("lib.rs").to_owned().into(), "fn foo() {}\n".to_string());
+        let call = Span::with_root_ctxt(main.start_pos + BytePos(12), main.start_pos + BytePos(17));
+        let def = Span::with_root_ctxt(lib.start_pos, lib.start_pos + BytePos(8));
+
+        let dst = Box::new(Shared { data: output.clone() });
+        let mut emitter = EmitterWriter::new(dst, Some(sm), false, false, false, None, false);
+        // `with_hyperlinks` only enables them when stderr is a terminal.
+        emitter.hyperlinks = Some(HyperlinkTemplate::parse("file").unwrap());
+
+        let mut diag = Diagnostic::new(Level::Error, "this function takes 1 argument");
+        diag.code(DiagnosticId::Error("E0061".to_string()));
+        diag.set_span(call);
+        diag.span_label(def, "defined here");
+        emitter.emit_diagnostic(&diag);
+    });
+
+    let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+    let link_end = "\x1b]8;;\x1b\\";
+    assert!(output.starts_with(&format!(
+        "error[\x1b]8;;https://doc.rust-lang.org/error-index.html#E0061\x1b\\E0061{}]",
+        link_end
+    )));
+    assert!(output.contains("--> \x1b]8;;file://"));
+    assert!(output.contains(&format!("\x1b\\main.rs:1:13{}\n", link_end)));
+    assert!(output.contains("::: \x1b]8;;file://"));
+    assert!(output.contains(&format!("\x1b\\lib.rs:1:1{}\n", link_end)));
+}
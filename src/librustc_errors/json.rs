@@ -13,6 +13,7 @@ use rustc_span::source_map::{FilePathMapping, SourceMap};
 
 use crate::emitter::{Emitter, HumanReadableErrorType};
 use crate::registry::Registry;
+use crate::translation::Catalog;
 use crate::{Applicability, DiagnosticId};
 use crate::{CodeSuggestion, SubDiagnostic};
 
@@ -37,6 +38,7 @@ pub struct JsonEmitter {
     ui_testing: bool,
     json_rendered: HumanReadableErrorType,
     macro_backtrace: bool,
+    catalog: Option<Lrc<Catalog>>,
 }
 
 impl JsonEmitter {
@@ -55,6 +57,7 @@ impl JsonEmitter {
             ui_testing: false,
             json_rendered,
             macro_backtrace,
+            catalog: None,
         }
     }
 
@@ -89,17 +92,23 @@ impl JsonEmitter {
             ui_testing: false,
             json_rendered,
             macro_backtrace,
+            catalog: None,
         }
     }
 
     pub fn ui_testing(self, ui_testing: bool) -> Self {
         Self { ui_testing, ..self }
     }
+
+    pub fn with_catalog(self, catalog: Option<Lrc<Catalog>>) -> Self {
+        Self { catalog, ..self }
+    }
 }
 
 impl Emitter for JsonEmitter {
     fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
-        let data = Diagnostic::from_errors_diagnostic(diag, self);
+        let diag = self.translate_diagnostic(diag);
+        let data = Diagnostic::from_errors_diagnostic(&diag, self);
         let result = if self.pretty {
             writeln!(&mut self.dst, "{}", as_pretty_json(&data))
         } else {
@@ -126,6 +135,10 @@ impl Emitter for JsonEmitter {
         Some(&self.sm)
     }
 
+    fn catalog(&self) -> Option<&Catalog> {
+        self.catalog.as_deref()
+    }
+
     fn should_show_explain(&self) -> bool {
         match self.json_rendered {
             HumanReadableErrorType::Short(_) => false,
@@ -13,6 +13,9 @@ pub use emitter::ColorConfig;
 use log::debug;
 use Level::*;
 
+use apply::SuggestionApplier;
+use baseline::DiagnosticBaseline;
+use budget::ErrorBudget;
 use emitter::{is_case_difference, Emitter, EmitterWriter};
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
@@ -30,14 +33,19 @@ use std::{error, fmt};
 use termcolor::{Color, ColorSpec};
 
 pub mod annotate_snippet_emitter_writer;
+mod apply;
+pub mod baseline;
+mod budget;
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
 pub mod json;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
+pub mod translation;
 pub use snippet::Style;
 
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a>>;
@@ -266,8 +274,11 @@ impl fmt::Display for ExplicitBug {
 
 impl error::Error for ExplicitBug {}
 
-pub use diagnostic::{Diagnostic, DiagnosticId, DiagnosticStyledString, SubDiagnostic};
+pub use diagnostic::{
+    Diagnostic, DiagnosticId, DiagnosticItem, DiagnosticStyledString, SubDiagnostic,
+};
 pub use diagnostic_builder::DiagnosticBuilder;
+pub use translation::MessageId;
 
 /// A handler deals with errors and other compiler output.
 /// Certain errors (fatal, bug, unimpl) may cause immediate exit,
@@ -309,6 +320,19 @@ struct HandlerInner {
     /// The stashed diagnostics count towards the total error count.
     /// When `.abort_if_errors()` is called, these are also emitted.
     stashed_diagnostics: FxIndexMap<(Span, StashKey), Diagnostic>,
+
+    /// The known diagnostics to suppress, and where to record the emitted ones
+    /// (see `-Z diagnostic-baseline` and `-Z write-diagnostic-baseline`).
+    baseline: Option<DiagnosticBaseline>,
+
+    /// The suggestions to apply once the compilation is finished, if
+    /// `-Z apply-suggestions` was passed.
+    suggestion_applier: Option<SuggestionApplier>,
+
+    /// The limits on the number of errors shown and their summary, if
+    /// `-Z max-errors`, `-Z max-errors-per-code` or `-Z error-summary` was
+    /// passed.
+    error_budget: Option<ErrorBudget>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -322,6 +346,30 @@ fn default_track_diagnostic(_: &Diagnostic) {}
 pub static TRACK_DIAGNOSTICS: AtomicRef<fn(&Diagnostic)> =
     AtomicRef::new(&(default_track_diagnostic as fn(&_)));
 
+fn default_span_item(_: Span) -> Option<DiagnosticItem> {
+    None
+}
+
+/// Finds the item whose source contains a span, for the diagnostics which
+/// were created without their item (see `Diagnostic::item`).
+pub static SPAN_ITEM: AtomicRef<fn(Span) -> Option<DiagnosticItem>> =
+    AtomicRef::new(&(default_span_item as fn(_) -> _));
+
+/// Returns the item a diagnostic is about, for telling diagnostics apart
+/// across compilations and grouping them. When the item isn't known, like for
+/// the diagnostics of the parser, the file of the primary span stands in for
+/// both the item and its module.
+crate fn locate(diag: &Diagnostic, sm: Option<&SourceMap>) -> Option<DiagnosticItem> {
+    if let Some(item) = &diag.item {
+        return Some(item.clone());
+    }
+    let span = diag.span.primary_span().filter(|span| !span.is_dummy())?;
+    (*SPAN_ITEM)(span).or_else(|| {
+        let file = sm?.lookup_char_pos(span.lo()).file.name.to_string();
+        Some(DiagnosticItem { path: file.clone(), module: file })
+    })
+}
+
 #[derive(Copy, Clone, Default)]
 pub struct HandlerFlags {
     /// If false, warning-level lints are suppressed.
@@ -341,6 +389,19 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If true, machine applicable suggestions are applied to the source code.
+    /// (rustc: see `-Z apply-suggestions`)
+    pub apply_suggestions: bool,
+    /// If set, only this many errors are shown, and the others are summarized.
+    /// (rustc: see `-Z max-errors`)
+    pub max_errors: Option<usize>,
+    /// If set, only this many errors with each code are shown, and the others
+    /// are summarized.
+    /// (rustc: see `-Z max-errors-per-code`)
+    pub max_errors_per_code: Option<usize>,
+    /// If true, the errors are counted by code and module at the end.
+    /// (rustc: see `-Z error-summary`)
+    pub error_summary: bool,
 }
 
 impl Drop for HandlerInner {
@@ -417,6 +478,13 @@ impl Handler {
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
+                baseline: None,
+                suggestion_applier: if flags.apply_suggestions {
+                    Some(SuggestionApplier::default())
+                } else {
+                    None
+                },
+                error_budget: ErrorBudget::new(&flags),
             }),
         }
     }
@@ -443,6 +511,7 @@ impl Handler {
         inner.emitted_diagnostic_codes = Default::default();
         inner.emitted_diagnostics = Default::default();
         inner.stashed_diagnostics = Default::default();
+        inner.error_budget = ErrorBudget::new(&inner.flags);
     }
 
     /// Stash a given diagnostic with the given `Span` and `StashKey` as the key for later stealing.
@@ -669,6 +738,12 @@ impl Handler {
         self.inner.borrow().has_errors_or_delayed_span_bugs()
     }
 
+    /// Suppresses the diagnostics known to `baseline` from now on, and records
+    /// the others in it. The baseline is finished by `print_error_count`.
+    pub fn set_diagnostic_baseline(&self, baseline: DiagnosticBaseline) {
+        self.inner.borrow_mut().baseline = Some(baseline);
+    }
+
     pub fn print_error_count(&self, registry: &Registry) {
         self.inner.borrow_mut().print_error_count(registry)
     }
@@ -734,8 +809,15 @@ impl HandlerInner {
 
         (*TRACK_DIAGNOSTICS)(diagnostic);
 
-        if let Some(ref code) = diagnostic.code {
-            self.emitted_diagnostic_codes.insert(code.clone());
+        if let Some(baseline) = &mut self.baseline {
+            if baseline.suppress(diagnostic, self.emitter.source_map().map(|sm| &**sm)) {
+                return;
+            }
+        }
+
+        if let (Some(applier), Some(sm)) = (&mut self.suggestion_applier, self.emitter.source_map())
+        {
+            applier.collect(diagnostic, sm);
         }
 
         let already_emitted = |this: &mut Self| {
@@ -749,7 +831,18 @@ impl HandlerInner {
         // Only emit the diagnostic if we've been asked to deduplicate and
         // haven't already emitted an equivalent diagnostic.
         if !(self.flags.deduplicate_diagnostics && already_emitted(self)) {
-            self.emitter.emit_diagnostic(diagnostic);
+            // Errors beyond the budget are counted, but only summarized.
+            let shown = match &mut self.error_budget {
+                Some(budget) => budget.admit(diagnostic, self.emitter.source_map().map(|sm| &**sm)),
+                None => true,
+            };
+            if shown {
+                // Only the codes of the errors which were shown are explained.
+                if let Some(ref code) = diagnostic.code {
+                    self.emitted_diagnostic_codes.insert(code.clone());
+                }
+                self.emitter.emit_diagnostic(diagnostic);
+            }
             if diagnostic.is_error() {
                 self.deduplicated_err_count += 1;
             }
@@ -770,6 +863,26 @@ impl HandlerInner {
     fn print_error_count(&mut self, registry: &Registry) {
         self.emit_stashed_diagnostics();
 
+        if let Some(baseline) = self.baseline.take() {
+            for (level, message) in baseline.finish() {
+                self.emit_diagnostic(&Diagnostic::new(level, &message));
+            }
+        }
+        if let Some(applier) = self.suggestion_applier.take() {
+            let messages = match self.emitter.source_map() {
+                Some(sm) => applier.apply(sm),
+                None => vec![(Level::Warning, "suggestions cannot be applied".to_string())],
+            };
+            for (level, message) in messages {
+                self.emit_diagnostic(&Diagnostic::new(level, &message));
+            }
+        }
+        if let Some(budget) = self.error_budget.take() {
+            for (level, message) in budget.finish() {
+                self.emit_diagnostic(&Diagnostic::new(level, &message));
+            }
+        }
+
         let s = match self.deduplicated_err_count {
             0 => return,
             1 => "aborting due to previous error".to_string(),
@@ -796,6 +909,8 @@ impl HandlerInner {
                 .collect::<Vec<_>>();
             if !error_codes.is_empty() {
                 error_codes.sort();
+                let error_codes: Vec<_> =
+                    error_codes.iter().map(|code| self.emitter.format_error_code(code)).collect();
                 if error_codes.len() > 1 {
                     let limit = if error_codes.len() > 9 { 9 } else { error_codes.len() };
                     self.failure(&format!(
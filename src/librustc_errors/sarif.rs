@@ -0,0 +1,271 @@
+//! A SARIF emitter for errors.
+//!
+//! SARIF (Static Analysis Results Interchange Format) 2.1.0 is the format
+//! expected by code scanning and code review tools. Unlike the JSON emitter,
+//! which writes every diagnostic as soon as it is emitted, this emitter
+//! collects the diagnostics and writes a single SARIF log once it is dropped,
+//! i.e. at the end of the compilation.
+//!
+//! Diagnostics are mapped to SARIF results as follows:
+//!
+//! - The error code or lint name is the rule of the result.
+//! - Primary spans are the locations of the result, secondary spans and
+//!   the spans of child diagnostics are its related locations.
+//! - Child diagnostics without a span are appended to the message.
+//! - Suggestions are the fixes of the result.
+//!
+//! Diagnostics without any span, like "aborting due to previous error", are
+//! about the compilation rather than the code, so they are reported as
+//! notifications of the invocation instead.
+
+use rustc_span::source_map::SourceMap;
+
+use crate::emitter::Emitter;
+use crate::translation::Catalog;
+use crate::{CodeSuggestion, Diagnostic, DiagnosticId, Level};
+
+use rustc_data_structures::sync::Lrc;
+use rustc_serialize::json::Json;
+use rustc_span::{MultiSpan, Span};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str =
+    "https://schemastore.azurewebsites.net/schemas/json/sarif-2.1.0-rtm.5.json";
+const ERROR_INDEX_URI: &str = "https://doc.rust-lang.org/error-index.html";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    sm: Lrc<SourceMap>,
+    /// The rules of the results, with the URI of their documentation if any.
+    rules: BTreeMap<String, Option<String>>,
+    results: Vec<Json>,
+    notifications: Vec<Json>,
+    has_errors: bool,
+    catalog: Option<Lrc<Catalog>>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter::new(Box::new(io::stderr()), source_map)
+    }
+
+    pub fn new(dst: Box<dyn Write + Send>, source_map: Lrc<SourceMap>) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            sm: source_map,
+            rules: BTreeMap::new(),
+            results: Vec::new(),
+            notifications: Vec::new(),
+            has_errors: false,
+            catalog: None,
+        }
+    }
+
+    pub fn with_catalog(mut self, catalog: Option<Lrc<Catalog>>) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    fn region(&self, span: Span) -> Json {
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        object(vec![
+            ("startLine", Json::U64(start.line as u64)),
+            ("startColumn", Json::U64(start.col.0 as u64 + 1)),
+            ("endLine", Json::U64(end.line as u64)),
+            ("endColumn", Json::U64(end.col.0 as u64 + 1)),
+        ])
+    }
+
+    fn artifact_location(&self, span: Span) -> Json {
+        let file = self.sm.lookup_char_pos(span.lo()).file;
+        // SARIF URIs always use forward slashes.
+        object(vec![("uri", Json::String(file.name.to_string().replace('\\', "/")))])
+    }
+
+    fn location(&self, span: Span, message: Option<String>) -> Json {
+        let physical_location = object(vec![
+            ("artifactLocation", self.artifact_location(span)),
+            ("region", self.region(span)),
+        ]);
+        let mut fields = vec![("physicalLocation", physical_location)];
+        if let Some(message) = message {
+            fields.push(("message", text(message)));
+        }
+        object(fields)
+    }
+
+    fn fixes(&self, suggestion: &CodeSuggestion) -> Vec<Json> {
+        // Every substitution is an alternative way of applying the suggestion.
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                let changes = substitution
+                    .parts
+                    .iter()
+                    .map(|part| {
+                        let replacement = object(vec![
+                            ("deletedRegion", self.region(part.span)),
+                            (
+                                "insertedContent",
+                                object(vec![("text", Json::String(part.snippet.clone()))]),
+                            ),
+                        ]);
+                        object(vec![
+                            ("artifactLocation", self.artifact_location(part.span)),
+                            ("replacements", Json::Array(vec![replacement])),
+                        ])
+                    })
+                    .collect();
+                let applicability = format!("{:?}", suggestion.applicability);
+                object(vec![
+                    ("description", text(suggestion.msg.clone())),
+                    ("artifactChanges", Json::Array(changes)),
+                    ("properties", object(vec![("applicability", Json::String(applicability))])),
+                ])
+            })
+            .collect()
+    }
+
+    fn log(&self) -> Json {
+        let rules = self
+            .rules
+            .iter()
+            .map(|(id, help_uri)| {
+                let mut fields = vec![("id", Json::String(id.clone()))];
+                if let Some(help_uri) = help_uri {
+                    fields.push(("helpUri", Json::String(help_uri.clone())));
+                }
+                object(fields)
+            })
+            .collect();
+        let driver = object(vec![
+            ("name", Json::String("rustc".to_string())),
+            ("informationUri", Json::String("https://www.rust-lang.org/".to_string())),
+            ("rules", Json::Array(rules)),
+        ]);
+        let invocation = object(vec![
+            ("executionSuccessful", Json::Boolean(!self.has_errors)),
+            ("toolExecutionNotifications", Json::Array(self.notifications.clone())),
+        ]);
+        let run = object(vec![
+            ("tool", object(vec![("driver", driver)])),
+            ("invocations", Json::Array(vec![invocation])),
+            // Columns are character offsets, not UTF-16 offsets.
+            ("columnKind", Json::String("unicodeCodePoints".to_string())),
+            ("results", Json::Array(self.results.clone())),
+        ]);
+        object(vec![
+            ("$schema", Json::String(SARIF_SCHEMA.to_string())),
+            ("version", Json::String("2.1.0".to_string())),
+            ("runs", Json::Array(vec![run])),
+        ])
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let translated = self.translate_diagnostic(diag);
+        let diag = &*translated;
+        let level = sarif_level(diag.level);
+        self.has_errors |= level == "error";
+
+        if diag.span.primary_spans().is_empty() {
+            let notification = object(vec![
+                ("level", Json::String(level.to_string())),
+                ("message", text(diag.message())),
+            ]);
+            self.notifications.push(notification);
+            return;
+        }
+
+        let mut message = diag.message();
+        let mut locations = Vec::new();
+        let mut related_locations = Vec::new();
+        for span_label in diag.span.span_labels() {
+            let location = self.location(span_label.span, span_label.label);
+            if span_label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+        for child in &diag.children {
+            let span: &MultiSpan = child.render_span.as_ref().unwrap_or(&child.span);
+            let child_message = format!("{}: {}", child.level, child.message());
+            if span.primary_spans().is_empty() {
+                message.push_str("\n");
+                message.push_str(&child_message);
+            }
+            for &span in span.primary_spans() {
+                related_locations.push(self.location(span, Some(child_message.clone())));
+            }
+        }
+        let fixes: Vec<_> = diag.suggestions.iter().flat_map(|s| self.fixes(s)).collect();
+
+        let mut fields = Vec::new();
+        if let Some(code) = &diag.code {
+            let (id, help_uri) = match code {
+                DiagnosticId::Error(code) => {
+                    (code.clone(), Some(format!("{}#{}", ERROR_INDEX_URI, code)))
+                }
+                DiagnosticId::Lint(name) => (name.clone(), None),
+            };
+            fields.push(("ruleId", Json::String(id.clone())));
+            self.rules.insert(id, help_uri);
+        }
+        fields.push(("level", Json::String(level.to_string())));
+        fields.push(("message", text(message)));
+        fields.push(("locations", Json::Array(locations)));
+        if !related_locations.is_empty() {
+            fields.push(("relatedLocations", Json::Array(related_locations)));
+        }
+        if !fixes.is_empty() {
+            fields.push(("fixes", Json::Array(fixes)));
+        }
+        self.results.push(object(fields));
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn catalog(&self) -> Option<&Catalog> {
+        self.catalog.as_deref()
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = self.log();
+        // Panicking here could abort the process while it is unwinding.
+        if let Err(e) = writeln!(&mut self.dst, "{}", log.pretty()) {
+            let _ = writeln!(io::stderr(), "failed to write the SARIF log: {}", e);
+        }
+    }
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::Fatal | Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::Help | Level::Cancelled | Level::FailureNote => "note",
+    }
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect())
+}
+
+fn text(message: String) -> Json {
+    object(vec![("text", Json::String(message))])
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+use crate::{Applicability, Handler};
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+fn with_default_globals(f: impl FnOnce()) {
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || rustc_span::GLOBALS.set(&globals, f))
+}
+
+/// Emits diagnostics for `code` and returns the SARIF log.
+fn emit(code: &str, f: impl FnOnce(&Handler)) -> Json {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    with_default_globals(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+
+        let emitter = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm);
+        let handler = Handler::with_emitter(true, None, Box::new(emitter));
+        f(&handler);
+    });
+
+    let bytes = output.lock().unwrap();
+    Json::from_str(str::from_utf8(&bytes).unwrap()).unwrap()
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn result() {
+    let log = emit("let x = 1;\nlet y = x;", |handler| {
+        let mut diag = handler.struct_span_err(span(15, 16), "foo");
+        diag.code(DiagnosticId::Error("E0999".to_string()));
+        diag.span_label(span(15, 16), "primary label");
+        diag.span_label(span(4, 5), "secondary label");
+        diag.note("a note");
+        diag.span_suggestion(
+            span(15, 16),
+            "use `z`",
+            "z".to_string(),
+            Applicability::MaybeIncorrect,
+        );
+        diag.emit();
+    });
+
+    let run = &log["runs"][0];
+    assert_eq!(log["version"], Json::String("2.1.0".to_string()));
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], Json::String("E0999".to_string()));
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], Json::String("E0999".to_string()));
+    assert_eq!(result["level"], Json::String("error".to_string()));
+    assert_eq!(result["message"]["text"], Json::String("foo\nnote: a note".to_string()));
+
+    let location = &result["locations"][0];
+    let region = &location["physicalLocation"]["region"];
+    assert_eq!(
+        location["physicalLocation"]["artifactLocation"]["uri"],
+        Json::String("test.rs".to_string())
+    );
+    assert_eq!(region["startLine"], Json::U64(2));
+    assert_eq!(region["startColumn"], Json::U64(5));
+    assert_eq!(region["endColumn"], Json::U64(6));
+    assert_eq!(location["message"]["text"], Json::String("primary label".to_string()));
+
+    let related_location = &result["relatedLocations"][0];
+    assert_eq!(related_location["physicalLocation"]["region"]["startLine"], Json::U64(1));
+    assert_eq!(related_location["message"]["text"], Json::String("secondary label".to_string()));
+
+    let fix = &result["fixes"][0];
+    let replacement = &fix["artifactChanges"][0]["replacements"][0];
+    assert_eq!(fix["description"]["text"], Json::String("use `z`".to_string()));
+    assert_eq!(replacement["insertedContent"]["text"], Json::String("z".to_string()));
+    assert_eq!(replacement["deletedRegion"]["startColumn"], Json::U64(5));
+
+    assert_eq!(run["invocations"][0]["executionSuccessful"], Json::Boolean(false));
+}
+
+#[test]
+fn notification() {
+    let log = emit("", |handler| {
+        handler.warn("foo");
+    });
+
+    let run = &log["runs"][0];
+    let notification = &run["invocations"][0]["toolExecutionNotifications"][0];
+    assert_eq!(notification["level"], Json::String("warning".to_string()));
+    assert_eq!(notification["message"]["text"], Json::String("foo".to_string()));
+    assert_eq!(run["results"], Json::Array(vec![]));
+    assert_eq!(run["invocations"][0]["executionSuccessful"], Json::Boolean(true));
+}
@@ -0,0 +1,194 @@
+//! Translation of diagnostic messages (see `-Z translation-lang`).
+//!
+//! Diagnostics are written in English. The ones which can be translated also
+//! carry a `MessageId`: the identifier of the message in the translation
+//! catalogs, with the arguments it is formatted with. The identifiers are only
+//! resolved by the emitters, so diagnostics stay in English until they are
+//! emitted, e.g. when they are stored in the incremental cache. Messages
+//! without an identifier, or missing from the catalog, are emitted in English.
+//!
+//! The catalog of a language is the file `LANG/rustc.ftl` of the translation
+//! directory, written in a subset of the Fluent syntax:
+//!
+//! ```text
+//! # A comment.
+//! borrowck-move-borrowed = cannot move out of `{ $desc }` because it is borrowed
+//! ```
+//!
+//! A message continues on the following lines which are indented, and these
+//! lines are joined with newlines.
+
+use crate::snippet::Style;
+use crate::Diagnostic;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_span::MultiSpan;
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// The identifier of a message in the translation catalogs, with the
+/// arguments it is formatted with.
+#[derive(Clone, Debug, PartialEq, Hash, RustcEncodable, RustcDecodable)]
+pub struct MessageId {
+    pub id: String,
+    pub args: Vec<(String, String)>,
+}
+
+impl MessageId {
+    pub fn new(id: &str) -> MessageId {
+        MessageId { id: id.to_string(), args: Vec::new() }
+    }
+
+    /// Adds an argument, which is formatted right away: the catalogs only
+    /// decide where it goes in the message.
+    pub fn arg(mut self, name: &str, value: impl fmt::Display) -> MessageId {
+        self.args.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Piece {
+    Text(String),
+    Arg(String),
+}
+
+#[derive(Debug)]
+pub struct Catalog {
+    messages: FxHashMap<String, Vec<Piece>>,
+}
+
+impl Catalog {
+    /// Loads the catalog of `lang` from the translation directory `dir`.
+    pub fn load(dir: &Path, lang: &str) -> io::Result<Catalog> {
+        let src = fs::read_to_string(dir.join(lang).join("rustc.ftl"))?;
+        Catalog::parse(&src).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn parse(src: &str) -> Result<Catalog, String> {
+        let mut messages = FxHashMap::default();
+        let mut current: Option<(String, String, usize)> = None;
+        for (i, line) in src.lines().enumerate() {
+            if line.starts_with(|c: char| c == ' ' || c == '\t') && !line.trim().is_empty() {
+                match &mut current {
+                    Some((_, text, _)) => {
+                        text.push('\n');
+                        text.push_str(line.trim());
+                    }
+                    None => return Err(format!("line {}: unexpected continuation line", i + 1)),
+                }
+                continue;
+            }
+            if let Some((id, text, line)) = current.take() {
+                messages.insert(id, parse_message(&text, line)?);
+            }
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (id, text) = match line.find('=') {
+                Some(eq) => (line[..eq].trim(), line[eq + 1..].trim()),
+                None => return Err(format!("line {}: expected `=`", i + 1)),
+            };
+            if !is_identifier(id) {
+                return Err(format!("line {}: invalid message identifier `{}`", i + 1, id));
+            }
+            current = Some((id.to_string(), text.to_string(), i + 1));
+        }
+        if let Some((id, text, line)) = current {
+            messages.insert(id, parse_message(&text, line)?);
+        }
+        Ok(Catalog { messages })
+    }
+
+    /// Returns the translation of a message, or `None` if the catalog
+    /// doesn't have it or it uses arguments the message doesn't have.
+    pub fn translate(&self, id: &MessageId) -> Option<String> {
+        let mut message = String::new();
+        for piece in self.messages.get(&id.id)? {
+            match piece {
+                Piece::Text(text) => message.push_str(text),
+                Piece::Arg(name) => {
+                    let (_, value) = id.args.iter().find(|(n, _)| n == name)?;
+                    message.push_str(value);
+                }
+            }
+        }
+        Some(message)
+    }
+
+    /// Translates the messages of a diagnostic which have an identifier.
+    pub fn translate_diagnostic<'a>(&self, diag: &'a Diagnostic) -> Cow<'a, Diagnostic> {
+        let has_ids = diag.message_id.is_some()
+            || !diag.label_ids.is_empty()
+            || diag.children.iter().any(|child| child.message_id.is_some());
+        if !has_ids {
+            return Cow::Borrowed(diag);
+        }
+
+        let mut diag = diag.clone();
+        if let Some(message) = diag.message_id.as_ref().and_then(|id| self.translate(id)) {
+            diag.message = vec![(message, Style::NoStyle)];
+        }
+        for child in &mut diag.children {
+            if let Some(message) = child.message_id.as_ref().and_then(|id| self.translate(id)) {
+                child.message = vec![(message, Style::NoStyle)];
+            }
+        }
+        if !diag.label_ids.is_empty() {
+            // The labels of a `MultiSpan` can't be changed, so it is rebuilt.
+            let mut span = MultiSpan::from_spans(diag.span.primary_spans().to_vec());
+            for span_label in diag.span.span_labels() {
+                if let Some(label) = span_label.label {
+                    let label = diag
+                        .label_ids
+                        .iter()
+                        .find(|(sp, english, _)| *sp == span_label.span && *english == label)
+                        .and_then(|(_, _, id)| self.translate(id))
+                        .unwrap_or(label);
+                    span.push_span_label(span_label.span, label);
+                }
+            }
+            diag.span = span;
+        }
+        Cow::Owned(diag)
+    }
+}
+
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Splits a message into its text and its `{ $arg }` placeholders.
+fn parse_message(text: &str, line: usize) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            pieces.push(Piece::Text(rest[..open].to_string()));
+        }
+        let close = match rest[open..].find('}') {
+            Some(close) => open + close,
+            None => return Err(format!("line {}: unterminated placeholder", line)),
+        };
+        let placeholder = rest[open + 1..close].trim();
+        if !placeholder.starts_with('$') || !is_identifier(&placeholder[1..]) {
+            return Err(format!("line {}: invalid placeholder `{{{}}}`", line, placeholder));
+        }
+        pieces.push(Piece::Arg(placeholder[1..].to_string()));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest.to_string()));
+    }
+    Ok(pieces)
+}
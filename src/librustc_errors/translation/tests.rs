@@ -0,0 +1,81 @@
+use super::*;
+
+use crate::Level;
+use rustc_span::{BytePos, Span};
+
+fn with_default_globals(f: impl FnOnce()) {
+    let globals = rustc_span::Globals::new(rustc_span::edition::DEFAULT_EDITION);
+    rustc_span::GLOBALS.set(&globals, || rustc_span::GLOBALS.set(&globals, f))
+}
+
+const CATALOG: &str = "\
+# Messages of the tests.
+move-borrowed = impossible de déplacer `{ $desc }` car il est emprunté
+borrow-here = `{$desc}` est emprunté ici
+
+multi-line = première ligne
+    seconde ligne
+";
+
+#[test]
+fn parse() {
+    let catalog = Catalog::parse(CATALOG).unwrap();
+    let id = MessageId::new("move-borrowed").arg("desc", "x");
+    assert_eq!(catalog.translate(&id).unwrap(), "impossible de déplacer `x` car il est emprunté");
+    let id = MessageId::new("multi-line");
+    assert_eq!(catalog.translate(&id).unwrap(), "première ligne\nseconde ligne");
+
+    // Unknown messages and missing arguments are left untranslated.
+    assert_eq!(catalog.translate(&MessageId::new("unknown")), None);
+    assert_eq!(catalog.translate(&MessageId::new("borrow-here")), None);
+}
+
+#[test]
+fn malformed() {
+    assert!(Catalog::parse("no equal sign").unwrap_err().starts_with("line 1:"));
+    assert!(Catalog::parse("\n  continued").unwrap_err().starts_with("line 2:"));
+    assert!(Catalog::parse("a = { $b").unwrap_err().contains("unterminated"));
+    assert!(Catalog::parse("a = { b }").unwrap_err().contains("invalid placeholder"));
+    assert!(Catalog::parse("a b = c").unwrap_err().contains("invalid message identifier"));
+}
+
+#[test]
+fn translate_diagnostic() {
+    with_default_globals(|| {
+        let catalog = Catalog::parse(CATALOG).unwrap();
+        let span = Span::with_root_ctxt(BytePos(0), BytePos(1));
+        let other_span = Span::with_root_ctxt(BytePos(2), BytePos(3));
+
+        let mut diag =
+            Diagnostic::new(Level::Error, "cannot move out of `x` because it is borrowed");
+        diag.set_span(span);
+        diag.set_message_id(MessageId::new("move-borrowed").arg("desc", "x"));
+        diag.span_label_with_id(
+            other_span,
+            "borrow of `x` occurs here",
+            MessageId::new("borrow-here").arg("desc", "x"),
+        );
+        diag.span_label(span, "move out of `x` occurs here");
+        diag.note_with_id("an untranslated note", MessageId::new("unknown"));
+
+        let translated = catalog.translate_diagnostic(&diag);
+        assert_eq!(translated.message(), "impossible de déplacer `x` car il est emprunté");
+        let labels: Vec<_> =
+            translated.span.span_labels().into_iter().map(|l| (l.span, l.label)).collect();
+        assert_eq!(
+            labels,
+            vec![
+                (other_span, Some("`x` est emprunté ici".to_string())),
+                (span, Some("move out of `x` occurs here".to_string())),
+            ]
+        );
+        assert_eq!(translated.children[0].message(), "an untranslated note");
+
+        // Diagnostics without identifiers aren't copied.
+        let diag = Diagnostic::new(Level::Warning, "unused variable: `x`");
+        match catalog.translate_diagnostic(&diag) {
+            Cow::Borrowed(_) => {}
+            Cow::Owned(_) => panic!("the diagnostic was copied"),
+        }
+    });
+}
@@ -544,6 +544,20 @@ declare_features! (
     /// Allows the use of `no_sanitize` attribute.
     (active, no_sanitize, "1.42.0", Some(39699), None),
 
+    /// Allows terminating a test that runs for too long with `#[test_timeout]`.
+    (active, test_timeout, "1.43.0", None, None),
+
+    /// Allows `async fn` to be used as `#[test]` functions.
+    (active, async_test, "1.43.0", None, None),
+
+    /// Allows `#[must_not_suspend]` on types whose values shouldn't be held across
+    /// suspension points.
+    (active, must_not_suspend, "1.43.0", None, None),
+
+    /// Allows `#[has_significant_drop]` on types whose values shouldn't be dropped
+    /// right away by `let _ = ...`.
+    (active, has_significant_drop, "1.43.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
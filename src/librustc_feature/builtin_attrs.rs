@@ -314,6 +314,7 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     // Testing:
     gated!(allow_fail, Normal, template!(Word), experimental!(allow_fail)),
+    gated!(test_timeout, Normal, template!(NameValueStr: "seconds"), experimental!(test_timeout)),
     gated!(
         test_runner, CrateLevel, template!(List: "path"), custom_test_frameworks,
         "custom test frameworks are an unstable feature",
@@ -333,6 +334,14 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     gated!(ffi_returns_twice, Whitelisted, template!(Word), experimental!(ffi_returns_twice)),
     gated!(track_caller, Whitelisted, template!(Word), experimental!(track_caller)),
+    gated!(
+        must_not_suspend, Whitelisted, template!(Word, NameValueStr: "reason"),
+        experimental!(must_not_suspend),
+    ),
+    gated!(
+        has_significant_drop, Whitelisted, template!(Word, NameValueStr: "reason"),
+        experimental!(has_significant_drop),
+    ),
     gated!(
         register_attr, CrateLevel, template!(List: "attr1, attr2, ..."),
         experimental!(register_attr),
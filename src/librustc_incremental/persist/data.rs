@@ -1,6 +1,7 @@
 //! The data that we will serialize and deserialize.
 
 use rustc::dep_graph::{WorkProduct, WorkProductId};
+use std::path::PathBuf;
 
 #[derive(Debug, RustcEncodable, RustcDecodable)]
 pub struct SerializedWorkProduct {
@@ -10,3 +11,15 @@ pub struct SerializedWorkProduct {
     /// work-product data itself
     pub work_product: WorkProduct,
 }
+
+/// The workspace the cache was produced in (see the "Relocation" section of
+/// `fs.rs`).
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub struct SerializedWorkspace {
+    /// The directory the paths of the source files were hashed relative to.
+    pub root: Option<PathBuf>,
+
+    /// Whether the work products and query results contain absolute paths
+    /// into the workspace, like the working directory of the debuginfo.
+    pub embeds_root: bool,
+}
@@ -102,6 +102,24 @@
 //! It might be a good idea though to try and detect whether we are on an
 //! unsupported file system and emit a warning in that case. This is not yet
 //! implemented.
+//!
+//! ## Relocation
+//!
+//! The incremental compilation directory can be moved along with the workspace
+//! it belongs to, e.g. by a CI system restoring a cache produced in another
+//! checkout. Nothing in a session directory refers to its own location, and
+//! the dep-graph and the query result cache identify source files by their
+//! path relative to the workspace root, i.e. the working directory (see
+//! `FilePathMapping::stable_name`), or by their remapped path if
+//! `--remap-path-prefix` applies to them.
+//!
+//! The object files, however, contain the absolute paths of the source files
+//! in their debuginfo and panic locations, unless these were remapped, and so
+//! might some query results. Hence each session records its workspace root
+//! and whether the cached artifacts contain it. When a session finds that the
+//! workspace was moved and that they do, it discards the work products and the
+//! query result cache, but keeps the dep-graph: only the code generation is
+//! redone, not the analysis.
 
 use rustc::session::{CrateDisambiguator, Session};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -124,6 +142,7 @@ const LOCK_FILE_EXT: &str = ".lock";
 const DEP_GRAPH_FILENAME: &str = "dep-graph.bin";
 const WORK_PRODUCTS_FILENAME: &str = "work-products.bin";
 const QUERY_CACHE_FILENAME: &str = "query-cache.bin";
+const WORKSPACE_FILENAME: &str = "workspace.bin";
 
 // We encode integers using the following base, so they are shorter than decimal
 // or hexadecimal numbers (we want short file and directory names). Since these
@@ -146,6 +165,10 @@ pub fn query_cache_path(sess: &Session) -> PathBuf {
     in_incr_comp_dir_sess(sess, QUERY_CACHE_FILENAME)
 }
 
+pub fn workspace_path(sess: &Session) -> PathBuf {
+    in_incr_comp_dir_sess(sess, WORKSPACE_FILENAME)
+}
+
 pub fn lock_file_path(session_dir: &Path) -> PathBuf {
     let crate_dir = session_dir.parent().unwrap();
 
@@ -7,6 +7,8 @@ use rustc::ty::TyCtxt;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_serialize::opaque::Decoder;
 use rustc_serialize::Decodable as RustcDecodable;
+use std::fs;
+use std::io;
 use std::path::Path;
 
 use super::data::*;
@@ -72,6 +74,40 @@ fn delete_dirty_work_product(sess: &Session, swp: SerializedWorkProduct) {
     work_product::delete_workproduct_files(sess, &swp.work_product);
 }
 
+/// Returns whether the work products and query results of the previous session
+/// are invalid because the workspace was moved since, and they contain absolute
+/// paths into it. The dep-graph is independent of the workspace root.
+fn workspace_was_moved(sess: &Session) -> bool {
+    let report_incremental_info = sess.opts.debugging_opts.incremental_info;
+    let root = sess.source_map().path_mapping().workspace_root();
+
+    let prev = match load_data(report_incremental_info, &workspace_path(sess)) {
+        LoadResult::Ok { data: (bytes, start_pos) } => {
+            let mut decoder = Decoder::new(&bytes, start_pos);
+            match SerializedWorkspace::decode(&mut decoder) {
+                Ok(workspace) => workspace,
+                Err(_) => return true,
+            }
+        }
+        // The previous session did not record its workspace, so there's no
+        // telling where its artifacts point to.
+        _ => return true,
+    };
+
+    let prev_root = match prev.root {
+        Some(prev_root) if prev.embeds_root && Some(&*prev_root) != root => prev_root,
+        _ => return false,
+    };
+    if report_incremental_info {
+        println!(
+            "[incremental] discarding the work products and the query result cache, \
+             because the workspace was moved from `{}`",
+            prev_root.display()
+        );
+    }
+    true
+}
+
 /// Either a result that has already be computed or a
 /// handle that will let us wait until it is computed
 /// by a background thread.
@@ -119,6 +155,20 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
         let work_products_path = work_products_path(sess);
         let load_result = load_data(report_incremental_info, &work_products_path);
 
+        let workspace_was_moved = workspace_was_moved(sess);
+        if workspace_was_moved {
+            let query_cache_path = query_cache_path(sess);
+            match fs::remove_file(&query_cache_path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => sess.warn(&format!(
+                    "file-system error deleting outdated file `{}`: {}",
+                    query_cache_path.display(),
+                    err
+                )),
+            }
+        }
+
         if let LoadResult::Ok { data: (work_products_data, start_pos) } = load_result {
             // Decode the list of work_products
             let mut work_product_decoder = Decoder::new(&work_products_data[..], start_pos);
@@ -133,6 +183,12 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
                 });
 
             for swp in work_products {
+                if workspace_was_moved {
+                    debug!("reconcile_work_products: {:?} is from another workspace", swp);
+                    delete_dirty_work_product(sess, swp);
+                    continue;
+                }
+
                 let mut all_files_exist = true;
                 for &(_, ref file_name) in swp.work_product.saved_files.iter() {
                     let path = in_incr_comp_dir_sess(sess, file_name);
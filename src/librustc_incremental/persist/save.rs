@@ -1,10 +1,12 @@
 use rustc::dep_graph::{DepGraph, DepKind, WorkProduct, WorkProductId};
+use rustc::session::config::DebugInfo;
 use rustc::session::Session;
 use rustc::ty::TyCtxt;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::join;
 use rustc_serialize::opaque::Encoder;
 use rustc_serialize::Encodable as RustcEncodable;
+use rustc_span::FileName;
 use std::fs;
 use std::path::PathBuf;
 
@@ -28,6 +30,7 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
 
         let query_cache_path = query_cache_path(sess);
         let dep_graph_path = dep_graph_path(sess);
+        let workspace_path = workspace_path(sess);
 
         join(
             move || {
@@ -45,6 +48,7 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
                 });
             },
         );
+        save_in(sess, workspace_path, |e| encode_workspace(sess, e));
 
         dirty_clean::check_dirty_clean_annotations(tcx);
     })
@@ -249,3 +253,23 @@ fn encode_query_cache(tcx: TyCtxt<'_>, encoder: &mut Encoder) {
         tcx.serialize_query_result_cache(encoder).unwrap();
     })
 }
+
+fn encode_workspace(sess: &Session, encoder: &mut Encoder) {
+    let source_map = sess.source_map();
+    let root = source_map.path_mapping().workspace_root().map(|root| root.to_path_buf());
+    let embeds_root = match &root {
+        Some(root) => {
+            // The debuginfo records the working directory, and the paths of
+            // the source files are used as they are in debuginfo and panic
+            // locations.
+            (sess.opts.debuginfo != DebugInfo::None && !sess.working_dir.1)
+                || source_map.files().iter().any(|file| match &file.name {
+                    FileName::Real(path) => !file.name_was_remapped && path.starts_with(root),
+                    _ => false,
+                })
+        }
+        None => false,
+    };
+
+    SerializedWorkspace { root, embeds_root }.encode(encoder).unwrap();
+}
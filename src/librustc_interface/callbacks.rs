@@ -10,7 +10,7 @@
 //! origin crate when the `TyCtxt` is not present in TLS.
 
 use rustc::ty::tls;
-use rustc_errors::{Diagnostic, TRACK_DIAGNOSTICS};
+use rustc_errors::{Diagnostic, DiagnosticItem, SPAN_ITEM, TRACK_DIAGNOSTICS};
 use std::fmt;
 
 /// This is a callback from libsyntax as it cannot access the implicit state
@@ -39,6 +39,28 @@ fn track_diagnostic(diagnostic: &Diagnostic) {
     })
 }
 
+/// This is a callback from librustc_errors as it cannot access the implicit
+/// state in librustc otherwise. It is used to find the innermost item whose
+/// source contains the span of a diagnostic, once the HIR is built.
+fn span_item(span: rustc_span::Span) -> Option<DiagnosticItem> {
+    tls::with_opt(|tcx| {
+        let tcx = tcx?;
+        let span = span.source_callsite();
+        // Where a diagnostic is reported doesn't change the result of the
+        // query reporting it, so the crate is not tracked.
+        let krate = tcx.hir().untracked_krate();
+        let items = krate.items.values().map(|item| (item.hir_id, item.span));
+        let trait_items = krate.trait_items.values().map(|item| (item.hir_id, item.span));
+        let impl_items = krate.impl_items.values().map(|item| (item.hir_id, item.span));
+        let (hir_id, _) = items
+            .chain(trait_items)
+            .chain(impl_items)
+            .filter(|(_, item_span)| item_span.contains(span))
+            .min_by_key(|(_, item_span)| item_span.hi() - item_span.lo())?;
+        Some(tcx.diagnostic_location(hir_id))
+    })
+}
+
 /// This is a callback from librustc_hir as it cannot access the implicit state
 /// in librustc otherwise.
 fn def_id_debug(def_id: rustc_hir::def_id::DefId, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -58,4 +80,5 @@ pub fn setup_callbacks() {
     rustc_span::SPAN_DEBUG.swap(&(span_debug as fn(_, &mut fmt::Formatter<'_>) -> _));
     rustc_hir::def_id::DEF_ID_DEBUG.swap(&(def_id_debug as fn(_, &mut fmt::Formatter<'_>) -> _));
     TRACK_DIAGNOSTICS.swap(&(track_diagnostic as fn(&_)));
+    SPAN_ITEM.swap(&(span_item as fn(_) -> _));
 }
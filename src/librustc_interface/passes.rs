@@ -969,6 +969,10 @@ pub fn start_codegen<'tcx>(
         tcx.print_debug_stats();
     }
 
+    if tcx.sess.opts.debugging_opts.print_stack_frame_sizes {
+        mir::transform::check_stack_frame_size::record_stack_frame_sizes(tcx);
+    }
+
     if tcx.sess.opts.output_types.contains_key(&OutputType::Mir) {
         if let Err(e) = mir::transform::dump_mir::emit_mir(tcx, outputs) {
             tcx.sess.err(&format!("could not emit MIR: {}", e));
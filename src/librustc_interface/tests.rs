@@ -4,9 +4,10 @@ use crate::interface::parse_cfgspecs;
 
 use rustc::lint::Level;
 use rustc::middle::cstore;
+use rustc::session::config::SwitchWithOptPath;
 use rustc::session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc::session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
-use rustc::session::config::{ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
+use rustc::session::config::{ExternEntry, LinkerPluginLto, LintConfigEntry, LtoCli};
 use rustc::session::config::{Externs, OutputType, OutputTypes, SymbolManglingVersion};
 use rustc::session::search_paths::SearchPath;
 use rustc::session::{build_session, Session};
@@ -242,6 +243,27 @@ fn test_lints_tracking_hash_different_construction_order() {
     assert_eq!(v2.dep_tracking_hash(), v2.clone().dep_tracking_hash());
 }
 
+#[test]
+fn test_lint_config_tracking_hash() {
+    let entry = |name: &str, level| LintConfigEntry { name: name.to_string(), level, scope: None };
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let mut v3 = Options::default();
+
+    v1.lint_config = vec![entry("a", Level::Deny), entry("a", Level::Allow)];
+    v2.lint_config = vec![entry("a", Level::Allow), entry("a", Level::Deny)];
+    v3.lint_config = vec![entry("a", Level::Deny), entry("a", Level::Allow)];
+    v3.lint_config[1].scope = Some(PathBuf::from("src/generated"));
+
+    // Later entries take precedence, so the order matters.
+    assert!(v1.dep_tracking_hash() != v2.dep_tracking_hash());
+    assert!(v1.dep_tracking_hash() != v3.dep_tracking_hash());
+    assert!(v2.dep_tracking_hash() != v3.dep_tracking_hash());
+
+    // Check clone
+    assert_eq!(v1.dep_tracking_hash(), v1.clone().dep_tracking_hash());
+}
+
 #[test]
 fn test_search_paths_tracking_hash_different_order() {
     let mut v1 = Options::default();
@@ -523,6 +545,28 @@ fn test_debugging_options_tracking_hash() {
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.time_passes = true;
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.time_passes_json = Some(PathBuf::from("time-passes.json"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.diagnostic_baseline = Some(PathBuf::from("baseline"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.write_diagnostic_baseline = Some(PathBuf::from("baseline"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.apply_suggestions = true;
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.translation_lang = Some(String::from("fr"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.translation_dir = Some(PathBuf::from("locale"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.print_stack_frame_sizes = true;
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.terminal_hyperlinks = Some(String::from("vscode"));
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.max_errors = Some(10);
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.max_errors_per_code = Some(5);
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+    opts.debugging_opts.error_summary = true;
+    assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.time_llvm_passes = true;
     assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
     opts.debugging_opts.input_stats = true;
@@ -628,6 +672,10 @@ fn test_debugging_options_tracking_hash() {
     opts = reference.clone();
     opts.debugging_opts.symbol_mangling_version = SymbolManglingVersion::V0;
     assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.stack_frame_size_limit = Some(65536);
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
 }
 
 #[test]
@@ -21,12 +21,14 @@
 //! If you define a new `LateLintPass`, you will also need to add it to the
 //! `late_lint_methods!` invocation in `lib.rs`.
 
+use crate::types::is_repr_nullable_ptr;
 use crate::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
 use rustc::hir::map::Map;
 use rustc::traits::misc::can_type_implement_copy;
-use rustc::ty::{self, layout::VariantIdx, Ty, TyCtxt};
+use rustc::ty::layout::{LayoutOf, VariantIdx};
+use rustc::ty::{self, ParamEnv, Ty, TyCtxt};
 use rustc_ast_pretty::pprust::{self, expr_to_string};
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_feature::Stability;
 use rustc_feature::{deprecated_attributes, AttributeGate, AttributeTemplate, AttributeType};
@@ -40,6 +42,7 @@ use rustc_span::edition::Edition;
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::{kw, sym, Symbol};
 use rustc_span::{BytePos, Span};
+use rustc_target::spec::abi::Abi;
 use syntax::ast::{self, Expr};
 use syntax::attr::{self, HasAttrs};
 use syntax::tokenstream::{TokenStream, TokenTree};
@@ -2043,3 +2046,157 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InvalidValue {
         }
     }
 }
+
+declare_lint! {
+    pub CLASHING_EXTERN_DECLARATIONS,
+    Warn,
+    "detects when an extern fn has been declared with the same name but different types"
+}
+
+pub struct ClashingExternDeclarations {
+    /// The first declaration of each function linked to in the crate, by link name.
+    seen_decls: FxHashMap<Symbol, (DefId, Span)>,
+}
+
+impl_lint_pass!(ClashingExternDeclarations => [CLASHING_EXTERN_DECLARATIONS]);
+
+impl ClashingExternDeclarations {
+    pub fn new() -> Self {
+        ClashingExternDeclarations { seen_decls: FxHashMap::default() }
+    }
+
+    /// Replaces the types which have the same representation as a raw pointer
+    /// by that pointer: references, and the `Option`-like enums of references
+    /// the nullable pointer optimization applies to.
+    fn ffi_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Ty<'tcx> {
+        match ty.kind {
+            ty::Ref(_, ty, mutbl) => tcx.mk_ptr(ty::TypeAndMut { ty, mutbl }),
+            ty::Adt(def, substs) if def.is_enum() && is_repr_nullable_ptr(tcx, ty, def, substs) => {
+                // Only one of the two variants has a field.
+                let field_ty = def.all_fields().next().unwrap().ty(tcx, substs);
+                match field_ty.kind {
+                    ty::Ref(..) => Self::ffi_ty(tcx, field_ty),
+                    _ => ty,
+                }
+            }
+            _ => ty,
+        }
+    }
+
+    /// Checks whether two types have the same representation when passed to
+    /// or from foreign code. Pairs of types in `seen` are being compared
+    /// already, which makes recursive types compare equal when nothing else
+    /// differs.
+    fn structurally_same_type<'tcx>(
+        cx: &LateContext<'_, 'tcx>,
+        a: Ty<'tcx>,
+        b: Ty<'tcx>,
+        seen: &mut FxHashSet<(Ty<'tcx>, Ty<'tcx>)>,
+    ) -> bool {
+        let tcx = cx.tcx;
+        let a = Self::ffi_ty(tcx, tcx.normalize_erasing_regions(ParamEnv::reveal_all(), a));
+        let b = Self::ffi_ty(tcx, tcx.normalize_erasing_regions(ParamEnv::reveal_all(), b));
+        if a == b || !seen.insert((a, b)) {
+            return true;
+        }
+
+        match (&a.kind, &b.kind) {
+            (&ty::Adt(a_def, _), &ty::Adt(b_def, _)) if a_def.is_enum() || b_def.is_enum() => {
+                // Only the layout of an enum matters, as foreign code can't
+                // tell its variants apart anyway. Neither does its `repr`, as
+                // long as the layouts agree.
+                match (cx.layout_of(a), cx.layout_of(b)) {
+                    (Ok(a), Ok(b)) => {
+                        a.size == b.size && a.align.abi == b.align.abi && a.abi == b.abi
+                    }
+                    _ => false,
+                }
+            }
+            (&ty::Adt(a_def, a_substs), &ty::Adt(b_def, b_substs)) => {
+                let a_fields = &a_def.non_enum_variant().fields;
+                let b_fields = &b_def.non_enum_variant().fields;
+                a_def.is_union() == b_def.is_union()
+                    && a_def.repr == b_def.repr
+                    && a_fields.len() == b_fields.len()
+                    && a_fields.iter().zip(b_fields.iter()).all(|(a_field, b_field)| {
+                        let a_ty = a_field.ty(tcx, a_substs);
+                        let b_ty = b_field.ty(tcx, b_substs);
+                        Self::structurally_same_type(cx, a_ty, b_ty, seen)
+                    })
+            }
+            (&ty::Array(a_ty, a_len), &ty::Array(b_ty, b_len)) => {
+                let a_len = a_len.try_eval_usize(tcx, ParamEnv::reveal_all());
+                a_len.is_some()
+                    && a_len == b_len.try_eval_usize(tcx, ParamEnv::reveal_all())
+                    && Self::structurally_same_type(cx, a_ty, b_ty, seen)
+            }
+            (&ty::Slice(a_ty), &ty::Slice(b_ty)) => {
+                Self::structurally_same_type(cx, a_ty, b_ty, seen)
+            }
+            (&ty::RawPtr(a_tm), &ty::RawPtr(b_tm)) => {
+                a_tm.mutbl == b_tm.mutbl && Self::structurally_same_type(cx, a_tm.ty, b_tm.ty, seen)
+            }
+            (&ty::FnPtr(a_sig), &ty::FnPtr(b_sig)) => {
+                let a_sig = tcx.erase_late_bound_regions(&a_sig);
+                let b_sig = tcx.erase_late_bound_regions(&b_sig);
+                a_sig.abi == b_sig.abi
+                    && a_sig.c_variadic == b_sig.c_variadic
+                    && a_sig.inputs_and_output.len() == b_sig.inputs_and_output.len()
+                    && a_sig
+                        .inputs_and_output
+                        .iter()
+                        .zip(b_sig.inputs_and_output.iter())
+                        .all(|(a_ty, b_ty)| Self::structurally_same_type(cx, a_ty, b_ty, seen))
+            }
+            (&ty::Tuple(a_tys), &ty::Tuple(b_tys)) => {
+                a_tys.len() == b_tys.len()
+                    && a_tys
+                        .types()
+                        .zip(b_tys.types())
+                        .all(|(a_ty, b_ty)| Self::structurally_same_type(cx, a_ty, b_ty, seen))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ClashingExternDeclarations {
+    fn check_foreign_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &hir::ForeignItem<'_>) {
+        let tcx = cx.tcx;
+        match it.kind {
+            hir::ForeignItemKind::Fn(..) => {}
+            hir::ForeignItemKind::Static(..) | hir::ForeignItemKind::Type => return,
+        }
+        if let Abi::RustIntrinsic | Abi::PlatformIntrinsic = tcx.hir().get_foreign_abi(it.hir_id) {
+            // Intrinsics aren't linked to anything.
+            return;
+        }
+
+        let did = tcx.hir().local_def_id(it.hir_id);
+        let link_name = tcx.codegen_fn_attrs(did).link_name.unwrap_or(it.ident.name);
+        let (prev_did, prev_span) = match self.seen_decls.get(&link_name) {
+            Some(&prev) => prev,
+            None => {
+                self.seen_decls.insert(link_name, (did, it.span));
+                return;
+            }
+        };
+
+        let prev_ty = tcx.mk_fn_ptr(tcx.fn_sig(prev_did));
+        let this_ty = tcx.mk_fn_ptr(tcx.fn_sig(did));
+        if Self::structurally_same_type(cx, prev_ty, this_ty, &mut FxHashSet::default()) {
+            return;
+        }
+
+        let msg = if it.ident.name == link_name {
+            format!("`{}` redeclared with a different signature", it.ident)
+        } else {
+            format!("`{}` redeclares `{}` with a different signature", it.ident, link_name)
+        };
+        let mut err = cx.struct_span_lint(CLASHING_EXTERN_DECLARATIONS, it.span, &msg);
+        err.span_label(prev_span, format!("`{}` previously declared here", link_name));
+        err.span_label(it.span, "this signature doesn't match the previous declaration");
+        err.note(&format!("expected `{}`\n   found `{}`", prev_ty, this_ty));
+        err.emit();
+    }
+}
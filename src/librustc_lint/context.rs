@@ -562,6 +562,13 @@ pub trait LintContext: Sized {
             BuiltinLintDiagnostics::DeprecatedMacro(suggestion, span) => {
                 stability::deprecation_suggestion(&mut db, suggestion, span)
             }
+            BuiltinLintDiagnostics::UnusedCrateDependency(name) => {
+                db.help(&format!(
+                    "remove the dependency, or add `use {} as _;` if the crate is only needed \
+                     for its side effects",
+                    name
+                ));
+            }
         }
 
         db.emit();
@@ -0,0 +1,93 @@
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc::ty::{self, Ty};
+use rustc_hir as hir;
+use rustc_span::symbol::sym;
+
+declare_lint! {
+    pub DANGLING_POINTERS_FROM_TEMPORARIES,
+    Warn,
+    "detects getting a pointer from a temporary which is dropped right away"
+}
+
+declare_lint_pass!(
+    /// Checks for pointers into temporaries which are dropped at the end of
+    /// the statement, while the pointers are stored for later use.
+    DanglingPointers => [DANGLING_POINTERS_FROM_TEMPORARIES]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DanglingPointers {
+    fn check_local(&mut self, cx: &LateContext<'a, 'tcx>, local: &'tcx hir::Local<'tcx>) {
+        if let Some(init) = local.init {
+            lint_temporary_as_ptr(cx, init);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Assign(_, rhs, _) = expr.kind {
+            lint_temporary_as_ptr(cx, rhs);
+        }
+    }
+}
+
+/// Lints `expr`, a value which outlives the statement it is computed in, if it
+/// is a pointer returned by `as_ptr` or `as_mut_ptr` on a temporary owning the
+/// memory pointed to.
+fn lint_temporary_as_ptr<'tcx>(cx: &LateContext<'_, 'tcx>, mut expr: &'tcx hir::Expr<'tcx>) {
+    // Look through casts, like `CString::new(s).unwrap().as_ptr() as *const u8`.
+    while let hir::ExprKind::Cast(inner, _) | hir::ExprKind::Type(inner, _) = expr.kind {
+        expr = inner;
+    }
+
+    let (method, receiver) = match expr.kind {
+        hir::ExprKind::MethodCall(method, _, args) => (method, &args[0]),
+        _ => return,
+    };
+    if method.ident.name != sym::as_ptr && method.ident.name != sym::as_mut_ptr {
+        return;
+    }
+    if !matches!(cx.tables.expr_ty(expr).kind, ty::RawPtr(_)) {
+        return;
+    }
+
+    // Places, like local variables, outlive the statement: only the
+    // temporaries the receiver is evaluated into are dropped at its end.
+    if receiver.is_syntactic_place_expr() {
+        return;
+    }
+    let receiver_ty = cx.tables.expr_ty(receiver);
+    if !owns_pointee(cx, receiver_ty) {
+        return;
+    }
+
+    let msg = format!(
+        "getting the inner pointer of a temporary `{}` will result in a dangling pointer",
+        receiver_ty
+    );
+    let mut err = cx.struct_span_lint(DANGLING_POINTERS_FROM_TEMPORARIES, method.ident.span, &msg);
+    err.span_label(method.ident.span, "this pointer will be invalid");
+    err.span_label(
+        receiver.span,
+        format!("this `{}` is deallocated at the end of the statement", receiver_ty),
+    );
+    err.note(&format!(
+        "pointers do not have a lifetime; when calling `{}` the temporary is deallocated at \
+         the end of the statement because nothing is referencing it as far as the type \
+         system is concerned",
+        method.ident
+    ));
+    err.help("bind the temporary to a variable to extend its lifetime until the end of the block");
+    err.help("for more information, see https://doc.rust-lang.org/reference/destructors.html");
+    err.emit();
+}
+
+/// Returns whether dropping a value of type `ty` frees the memory the pointer
+/// returned by its `as_ptr` method points to.
+fn owns_pointee<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+    match ty.kind {
+        ty::Adt(..) if ty.is_box() => matches!(ty.boxed_ty().kind, ty::Slice(_) | ty::Str),
+        ty::Adt(def, _) => [sym::cstring_type, sym::string_type, sym::vec_type]
+            .iter()
+            .any(|&name| cx.tcx.is_diagnostic_item(name, def.did)),
+        _ => false,
+    }
+}
@@ -81,11 +81,22 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        // Modules loaded from their own file may have their own lint levels in
+        // the lint configuration file.
+        let config_push = match it.kind {
+            ast::ItemKind::Mod(ref module) if !module.inline => {
+                Some(self.context.builder.push_lint_config_scope(module.inner))
+            }
+            _ => None,
+        };
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             run_early_pass!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             run_early_pass!(cx, check_item_post, it);
-        })
+        });
+        if let Some(push) = config_push {
+            self.context.builder.pop(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
@@ -299,6 +310,7 @@ fn early_lint_crate<T: EarlyLintPass>(
     };
 
     // Visit the whole crate.
+    let config_push = cx.context.builder.push_lint_config_scope(krate.module.inner);
     cx.with_lint_attrs(ast::CRATE_NODE_ID, &krate.attrs, |cx| {
         // since the root module isn't visited as an item (because it isn't an
         // item), warn for it here.
@@ -308,6 +320,7 @@ fn early_lint_crate<T: EarlyLintPass>(
 
         run_early_pass!(cx, check_crate_post, krate);
     });
+    cx.context.builder.pop(config_push);
     cx.context.buffered
 }
 
@@ -1,7 +1,9 @@
 use crate::context::{CheckLintNameResult, LintStore};
 use crate::late::unerased_lint_store;
 use rustc::hir::map::Map;
-use rustc::lint::{struct_lint_level, LintLevelMap, LintLevelSets, LintSet, LintSource};
+use rustc::lint::{
+    struct_lint_level, LevelSource, LintLevelMap, LintLevelSets, LintSet, LintSource,
+};
 use rustc::ty::query::Providers;
 use rustc::ty::TyCtxt;
 use rustc_ast_pretty::pprust;
@@ -10,16 +12,18 @@ use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_hir::{intravisit, HirId};
-use rustc_session::lint::{builtin, Level, Lint};
+use rustc_session::lint::{builtin, Level, Lint, LintId};
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::source_map::MultiSpan;
 use rustc_span::symbol::{sym, Symbol};
+use rustc_span::{FileName, Span};
 use syntax::ast;
 use syntax::attr;
 use syntax::unwrap_or;
 
 use std::cmp;
+use std::path::PathBuf;
 
 fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> &LintLevelMap {
     assert_eq!(cnum, LOCAL_CRATE);
@@ -28,6 +32,7 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> &LintLevelMap {
     let mut builder = LintLevelMapBuilder { levels, tcx, store };
     let krate = tcx.hir().krate();
 
+    let config_push = builder.levels.push_lint_config_scope(krate.module.inner);
     let push = builder.levels.push(&krate.attrs, &store);
     builder.levels.register_id(hir::CRATE_HIR_ID);
     for macro_def in krate.exported_macros {
@@ -35,6 +40,7 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> &LintLevelMap {
     }
     intravisit::walk_crate(&mut builder, krate);
     builder.levels.pop(push);
+    builder.levels.pop(config_push);
 
     tcx.arena.alloc(builder.levels.build_map())
 }
@@ -45,6 +51,9 @@ pub struct LintLevelsBuilder<'a> {
     id_to_set: FxHashMap<HirId, u32>,
     cur: u32,
     warn_about_weird_lints: bool,
+    /// The entries of the lint configuration file which are scoped to a path
+    /// prefix, with the lints they set the level of.
+    scoped_lint_config: Vec<(PathBuf, Symbol, Level, Vec<LintId>)>,
 }
 
 pub struct BuilderPush {
@@ -60,9 +69,11 @@ impl<'a> LintLevelsBuilder<'a> {
             cur: 0,
             id_to_set: Default::default(),
             warn_about_weird_lints,
+            scoped_lint_config: Vec::new(),
         };
         builder.process_command_line(sess, store);
         assert_eq!(builder.sets.list.len(), 1);
+        builder.process_lint_config(sess, store);
         builder
     }
 
@@ -92,6 +103,119 @@ impl<'a> LintLevelsBuilder<'a> {
         self.sets.list.push(LintSet::CommandLine { specs });
     }
 
+    /// Adds the levels set by the lint configuration file (`--lint-config`)
+    /// on top of the command line ones. The entries scoped to a path prefix
+    /// are only added by `push_lint_config_scope`.
+    fn process_lint_config(&mut self, sess: &Session, store: &LintStore) {
+        let mut specs = FxHashMap::default();
+        for entry in &sess.opts.lint_config {
+            let ids = match self.check_lint_config_name(&entry.name, store) {
+                Some(ids) => ids,
+                None => continue,
+            };
+            let level = cmp::min(entry.level, self.sets.lint_cap);
+            let name = Symbol::intern(&entry.name);
+            match entry.scope {
+                Some(ref prefix) => {
+                    self.scoped_lint_config.push((prefix.clone(), name, level, ids));
+                }
+                None => {
+                    for id in ids {
+                        self.insert_lint_config_spec(&mut specs, id, level, name);
+                    }
+                }
+            }
+        }
+
+        if !specs.is_empty() {
+            self.cur = self.sets.list.len() as u32;
+            self.sets.list.push(LintSet::Node { specs, parent: 0 });
+        }
+    }
+
+    /// Returns the lints named by an entry of the lint configuration file, or
+    /// reports the name if it isn't a known lint.
+    fn check_lint_config_name(&self, name: &str, store: &LintStore) -> Option<Vec<LintId>> {
+        let (tool_name, lint_name) = match name.find("::") {
+            Some(i) => (Some(Symbol::intern(&name[..i])), &name[i + 2..]),
+            None => (None, name),
+        };
+        let (lint, msg, suggestion) = match store.check_lint_name(lint_name, tool_name) {
+            CheckLintNameResult::Ok(ids) => return Some(ids.to_vec()),
+            CheckLintNameResult::Tool(Ok(ids)) => return Some(ids.to_vec()),
+            CheckLintNameResult::Tool(Err((Some(ids), _))) => return Some(ids.to_vec()),
+            // The lint belongs to a tool which isn't running.
+            CheckLintNameResult::Tool(Err((None, _))) => return None,
+            CheckLintNameResult::Warning(msg, renamed) => {
+                (builtin::RENAMED_AND_REMOVED_LINTS, msg, renamed)
+            }
+            CheckLintNameResult::NoLint(suggestion) => (
+                builtin::UNKNOWN_LINTS,
+                format!("unknown lint: `{}`", name),
+                suggestion.map(|s| s.to_string()),
+            ),
+        };
+
+        if self.warn_about_weird_lints {
+            let (level, src) = self.sets.get_lint_level(lint, self.cur, None, self.sess);
+            let mut err = struct_lint_level(self.sess, lint, level, src, None, &msg);
+            err.note("requested in the lint configuration file");
+            if let Some(suggestion) = suggestion {
+                err.help(&format!("use `{}` instead", suggestion));
+            }
+            err.emit();
+        }
+        // Renamed lints still apply under their new name.
+        store.find_lints(name).ok()
+    }
+
+    /// Sets the level of a lint from the lint configuration file, unless it is
+    /// forbidden.
+    fn insert_lint_config_spec(
+        &self,
+        specs: &mut FxHashMap<LintId, LevelSource>,
+        id: LintId,
+        level: Level,
+        name: Symbol,
+    ) {
+        if level != Level::Forbid {
+            if let (Some(Level::Forbid), _) = self.sets.get_lint_id_level(id, self.cur, Some(specs))
+            {
+                return;
+            }
+        }
+        specs.insert(id, (level, LintSource::LintConfig(name)));
+    }
+
+    /// Pushes the levels the lint configuration file sets for the code in the
+    /// file of `inner`, the span of the contents of a module which was loaded
+    /// from its own file (or of the crate root).
+    ///
+    /// These levels take precedence over the ones of the enclosing modules, but
+    /// not over the attributes of the module itself, so they must be pushed
+    /// before them. Don't forget to call `pop`!
+    pub fn push_lint_config_scope(&mut self, inner: Span) -> BuilderPush {
+        let prev = self.cur;
+        let mut specs = FxHashMap::default();
+        if let FileName::Real(path) = self.sess.source_map().span_to_filename(inner) {
+            // The prefixes are absolute.
+            let path = self.sess.working_dir.0.join(path);
+            for (prefix, name, level, ids) in &self.scoped_lint_config {
+                if path.starts_with(prefix) {
+                    for &id in ids {
+                        self.insert_lint_config_spec(&mut specs, id, *level, *name);
+                    }
+                }
+            }
+        }
+
+        if !specs.is_empty() {
+            self.cur = self.sets.list.len() as u32;
+            self.sets.list.push(LintSet::Node { specs, parent: prev });
+        }
+        BuilderPush { prev, changed: prev != self.cur }
+    }
+
     /// Pushes a list of AST lint attributes onto this context.
     ///
     /// This function will return a `BuilderPush` object which should be passed
@@ -338,6 +462,7 @@ impl<'a> LintLevelsBuilder<'a> {
                 LintSource::Default => id.to_string(),
                 LintSource::Node(name, _, _) => name.to_string(),
                 LintSource::CommandLine(name) => name.to_string(),
+                LintSource::LintConfig(name) => name.to_string(),
             };
             let (lint_attr_name, lint_attr_span) = match *src {
                 LintSource::Node(name, span, _) => (name, span),
@@ -364,6 +489,9 @@ impl<'a> LintLevelsBuilder<'a> {
                 LintSource::CommandLine(_) => {
                     diag_builder.note("`forbid` lint level was set on command line");
                 }
+                LintSource::LintConfig(_) => {
+                    diag_builder.note("`forbid` lint level was set in the lint configuration file");
+                }
             }
             diag_builder.emit();
             // don't set a separate error for every lint in the group
@@ -445,9 +573,23 @@ impl<'tcx> intravisit::Visitor<'tcx> for LintLevelMapBuilder<'_, 'tcx> {
     }
 
     fn visit_item(&mut self, it: &'tcx hir::Item<'tcx>) {
+        // Modules loaded from their own file may have their own lint levels in
+        // the lint configuration file.
+        let config_push = match it.kind {
+            hir::ItemKind::Mod(ref module) if !it.span.contains(module.inner) => {
+                Some(self.levels.push_lint_config_scope(module.inner))
+            }
+            _ => None,
+        };
+        if config_push.as_ref().map_or(false, |push| push.changed) {
+            self.levels.register_id(it.hir_id);
+        }
         self.with_lint_attrs(it.hir_id, &it.attrs, |builder| {
             intravisit::walk_item(builder, it);
         });
+        if let Some(push) = config_push {
+            self.levels.pop(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'tcx hir::ForeignItem<'tcx>) {
@@ -43,6 +43,7 @@ extern crate rustc_session;
 mod array_into_iter;
 pub mod builtin;
 mod context;
+mod dangling_pointers;
 mod early;
 mod internal;
 mod late;
@@ -67,6 +68,7 @@ use syntax::ast;
 
 use array_into_iter::ArrayIntoIter;
 use builtin::*;
+use dangling_pointers::DanglingPointers;
 use internal::*;
 use non_ascii_idents::*;
 use nonstandard_style::*;
@@ -143,6 +145,8 @@ macro_rules! late_lint_passes {
                 UnstableFeatures: UnstableFeatures,
                 // Tracks state across modules
                 UnnameableTestItems: UnnameableTestItems::new(),
+                // Tracks state across modules
+                ClashingExternDeclarations: ClashingExternDeclarations::new(),
                 // Tracks attributes of parents
                 MissingDoc: MissingDoc::new(),
                 // Depends on access levels
@@ -150,6 +154,7 @@ macro_rules! late_lint_passes {
                 // and change this to a module lint pass
                 MissingDebugImplementations: MissingDebugImplementations::default(),
                 ArrayIntoIter: ArrayIntoIter,
+                DanglingPointers: DanglingPointers,
             ]
         );
     };
@@ -165,6 +170,7 @@ macro_rules! late_lint_mod_passes {
                 VariantSizeDifferences: VariantSizeDifferences,
                 BoxPointers: BoxPointers,
                 PathStatements: PathStatements,
+                LetUnderscore: LetUnderscore,
                 // Depends on referenced function signatures in expressions
                 UnusedResults: UnusedResults,
                 NonUpperCaseGlobals: NonUpperCaseGlobals,
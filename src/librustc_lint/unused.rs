@@ -3,7 +3,7 @@ use rustc::ty::adjustment;
 use rustc::ty::{self, Ty};
 use rustc_ast_pretty::pprust;
 use rustc_data_structures::fx::FxHashMap;
-use rustc_errors::{pluralize, Applicability};
+use rustc_errors::{pluralize, Applicability, DiagnosticBuilder};
 use rustc_feature::{AttributeType, BuiltinAttribute, BUILTIN_ATTRIBUTE_MAP};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
@@ -49,26 +49,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
 
         let mut fn_warned = false;
         let mut op_warned = false;
-        let maybe_def_id = match expr.kind {
-            hir::ExprKind::Call(ref callee, _) => {
-                match callee.kind {
-                    hir::ExprKind::Path(ref qpath) => {
-                        match cx.tables.qpath_res(qpath, callee.hir_id) {
-                            Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => {
-                                Some(def_id)
-                            }
-                            // `Res::Local` if it was a closure, for which we
-                            // do not currently support must-use linting
-                            _ => None,
-                        }
-                    }
-                    _ => None,
-                }
-            }
-            hir::ExprKind::MethodCall(..) => cx.tables.type_dependent_def_id(expr.hir_id),
-            _ => None,
-        };
-        if let Some(def_id) = maybe_def_id {
+        if let Some(def_id) = called_fn_def_id(cx, expr) {
             fn_warned = check_must_use_def(cx, def_id, s.span, "return value of ", "");
         } else if type_permits_lack_of_use {
             // We don't warn about unused unit or uninhabited types.
@@ -251,6 +232,134 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for PathStatements {
     }
 }
 
+declare_lint! {
+    pub LET_UNDERSCORE_DROP,
+    Warn,
+    "`let _ = expr` dropping a value whose drop is significant, like a lock guard, right away"
+}
+
+declare_lint! {
+    pub LET_UNDERSCORE_MUST_USE,
+    Allow,
+    "`let _ = expr` discarding a value which must be used"
+}
+
+declare_lint_pass!(LetUnderscore => [LET_UNDERSCORE_DROP, LET_UNDERSCORE_MUST_USE]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LetUnderscore {
+    fn check_local(&mut self, cx: &LateContext<'a, 'tcx>, local: &'tcx hir::Local<'tcx>) {
+        match local.pat.kind {
+            hir::PatKind::Wild => {}
+            _ => return,
+        }
+        let init = match local.init {
+            Some(init) => init,
+            None => return,
+        };
+        // Desugarings bind to `_` on purpose, e.g. the wildcard arguments of
+        // an `async fn`, which are moved into its body to be dropped there.
+        if local.span.from_expansion() {
+            return;
+        }
+
+        let ty = cx.tables.expr_ty(init);
+        if let Some(def_id) = significant_drop_ty(cx, ty) {
+            let msg =
+                format!("non-binding `let` drops the `{}` right away", cx.tcx.def_path_str(def_id));
+            let mut err = cx.struct_span_lint(LET_UNDERSCORE_DROP, local.span, &msg);
+            let attrs = cx.tcx.get_attrs(def_id);
+            if let Some(reason) =
+                attr::find_by_name(&attrs, sym::has_significant_drop).and_then(|a| a.value_str())
+            {
+                err.note(&reason.as_str());
+            }
+            suggest_binding(&mut err, local, init, "_guard");
+            err.emit();
+            return;
+        }
+
+        let msg = match (called_fn_def_id(cx, init), &ty.kind) {
+            (Some(def_id), _) if cx.tcx.has_attr(def_id, sym::must_use) => format!(
+                "non-binding `let` discards the return value of `{}`",
+                cx.tcx.def_path_str(def_id)
+            ),
+            (_, ty::Adt(def, _)) if cx.tcx.has_attr(def.did, sym::must_use) => {
+                format!("non-binding `let` discards a value of `#[must_use]` type `{}`", ty)
+            }
+            _ => return,
+        };
+        let mut err = cx.struct_span_lint(LET_UNDERSCORE_MUST_USE, local.span, &msg);
+        suggest_binding(&mut err, local, init, "_unused");
+        err.emit();
+    }
+}
+
+/// Returns the type with a significant drop (see `#[has_significant_drop]`)
+/// dropped with a value of type `ty`, e.g. the `MutexGuard` of the
+/// `LockResult<MutexGuard<'_, T>>` returned by `Mutex::lock`.
+fn significant_drop_ty<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> Option<DefId> {
+    // Values which aren't dropped can't contain a value which is, e.g. the
+    // `MutexGuard` of a `PhantomData<MutexGuard<'_, T>>` is never created.
+    if !ty.needs_drop(cx.tcx, cx.param_env) {
+        return None;
+    }
+    match ty.kind {
+        ty::Adt(def, substs) => {
+            if cx.tcx.has_attr(def.did, sym::has_significant_drop) {
+                return Some(def.did);
+            }
+            substs.types().find_map(|ty| significant_drop_ty(cx, ty))
+        }
+        ty::Tuple(..) => ty.tuple_fields().find_map(|ty| significant_drop_ty(cx, ty)),
+        ty::Array(ty, _) => significant_drop_ty(cx, ty),
+        _ => None,
+    }
+}
+
+/// Returns the function or method called by `expr`, if it is a call.
+fn called_fn_def_id(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<DefId> {
+    match expr.kind {
+        hir::ExprKind::Call(ref callee, _) => match callee.kind {
+            hir::ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, callee.hir_id) {
+                Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => Some(def_id),
+                // `Res::Local` if it was a closure, for which we
+                // do not currently support must-use linting
+                _ => None,
+            },
+            _ => None,
+        },
+        hir::ExprKind::MethodCall(..) => cx.tables.type_dependent_def_id(expr.hir_id),
+        _ => None,
+    }
+}
+
+/// Suggests binding the value of `let _ = init` to `name`, or dropping it
+/// explicitly. Only dropping it can be applied automatically: it doesn't change
+/// what the code does, while keeping the value alive may not be what was meant,
+/// e.g. a lock guard kept alive deadlocks when the lock is taken again later.
+fn suggest_binding(
+    err: &mut DiagnosticBuilder<'_>,
+    local: &hir::Local<'_>,
+    init: &hir::Expr<'_>,
+    name: &str,
+) {
+    err.span_suggestion(
+        local.pat.span,
+        "consider binding to an unused variable to keep the value alive until the end of the scope",
+        name.to_string(),
+        Applicability::MaybeIncorrect,
+    );
+    let init_span = init.span.source_callsite();
+    err.multipart_suggestion(
+        "consider dropping the value explicitly to show that it is dropped right away",
+        vec![
+            (local.span.until(init_span), "drop(".to_string()),
+            (init_span.shrink_to_hi(), ")".to_string()),
+        ],
+        Applicability::MachineApplicable,
+    );
+}
+
 #[derive(Copy, Clone)]
 pub struct UnusedAttributes {
     builtin_attributes: &'static FxHashMap<Symbol, &'static BuiltinAttribute>,
@@ -4,12 +4,15 @@ use crate::locator::{CrateLocator, CratePaths};
 use crate::rmeta::{CrateDep, CrateMetadata, CrateNumMap, CrateRoot, MetadataBlob};
 
 use rustc::hir::map::Definitions;
+use rustc::lint;
 use rustc::middle::cstore::DepKind;
 use rustc::middle::cstore::{CrateSource, ExternCrate, ExternCrateSource, MetadataLoaderDyn};
-use rustc::session::config;
+use rustc::session::config::{self, ExternLocation};
+use rustc::session::lint::BuiltinLintDiagnostics;
 use rustc::session::search_paths::PathKind;
 use rustc::session::{CrateDisambiguator, Session};
 use rustc::ty::TyCtxt;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::struct_span_err;
@@ -47,6 +50,9 @@ pub struct CrateLoader<'a> {
     local_crate_name: Symbol,
     // Mutable output.
     cstore: CStore,
+    /// The names of the crates passed with `--extern` which were loaded
+    /// by the local crate, rather than as dependencies of other crates.
+    used_extern_options: FxHashSet<Symbol>,
 }
 
 pub enum LoadedMacro {
@@ -181,6 +187,7 @@ impl<'a> CrateLoader<'a> {
                 allocator_kind: None,
                 has_global_allocator: false,
             },
+            used_extern_options: Default::default(),
         }
     }
 
@@ -421,6 +428,9 @@ impl<'a> CrateLoader<'a> {
         dep_kind: DepKind,
         dep: Option<(&'b CratePaths, &'b CrateDep)>,
     ) -> CrateNum {
+        if dep.is_none() {
+            self.used_extern_options.insert(name);
+        }
         self.maybe_resolve_crate(name, span, dep_kind, dep).unwrap_or_else(|err| err.report())
     }
 
@@ -820,11 +830,35 @@ impl<'a> CrateLoader<'a> {
         });
     }
 
+    fn report_unused_deps(&self, krate: &ast::Crate) {
+        // Point at the start of the crate rather than at all of it.
+        let span = krate.span.shrink_to_lo();
+        for (name, entry) in self.sess.opts.externs.iter() {
+            // A pathless `--extern name` only adds a crate of the sysroot to
+            // the extern prelude, like `--extern proc_macro` does.
+            if let ExternLocation::FoundInLibrarySearchDirectories = entry.location {
+                continue;
+            }
+            if self.used_extern_options.contains(&Symbol::intern(name)) {
+                continue;
+            }
+            self.sess.parse_sess.buffer_lint_with_diagnostic(
+                lint::builtin::UNUSED_CRATE_DEPENDENCIES,
+                span,
+                ast::CRATE_NODE_ID,
+                &format!("external crate `{}` is never used by `{}`", name, self.local_crate_name),
+                BuiltinLintDiagnostics::UnusedCrateDependency(Symbol::intern(name)),
+            );
+        }
+    }
+
     pub fn postprocess(&mut self, krate: &ast::Crate) {
         self.inject_profiler_runtime();
         self.inject_allocator_crate(krate);
         self.inject_panic_runtime(krate);
 
+        self.report_unused_deps(krate);
+
         if log_enabled!(log::Level::Info) {
             dump_crates(&self.cstore);
         }
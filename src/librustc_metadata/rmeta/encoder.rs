@@ -349,6 +349,8 @@ impl<'tcx> EncodeContext<'tcx> {
         let all_source_files = source_map.files();
 
         let (working_dir, _cwd_remapped) = self.tcx.sess.working_dir.clone();
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        let crate_disambiguator = self.tcx.sess.local_crate_disambiguator();
 
         let adapted = all_source_files
             .iter()
@@ -371,9 +373,16 @@ impl<'tcx> EncodeContext<'tcx> {
                     FileName::Real(ref name) => {
                         let mut adapted = (**source_file).clone();
                         adapted.name = Path::new(&working_dir).join(name).into();
+                        // The hash stays relative to the workspace root, so
+                        // that it doesn't change when the workspace is moved.
+                        // The crate is hashed as well, as the files of other
+                        // crates have the same names relative to their own
+                        // root, like `src/lib.rs`.
                         adapted.name_hash = {
                             let mut hasher: StableHasher = StableHasher::new();
-                            adapted.name.hash(&mut hasher);
+                            crate_name.hash(&mut hasher);
+                            crate_disambiguator.hash(&mut hasher);
+                            source_map.path_mapping().stable_name(&adapted.name).hash(&mut hasher);
                             hasher.finish::<u128>()
                         };
                         Lrc::new(adapted)
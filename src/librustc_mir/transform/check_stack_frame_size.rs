@@ -0,0 +1,152 @@
+//! Estimates the size of the stack frame of functions from the layouts of
+//! their locals, for `-Z stack-frame-size-limit` and
+//! `-Z print-stack-frame-sizes`.
+//!
+//! The estimate is the size the locals would take if each of them had its
+//! own stack slot. LLVM may give locals which are not live at the same time
+//! the same slot and remove others altogether, so the actual frame is
+//! usually smaller, but locals which are live at the same time are all
+//! counted, and these are what makes a frame grow beyond a small stack.
+
+use crate::transform::{MirPass, MirSource};
+use rustc::lint::builtin::LARGE_STACK_FRAMES;
+use rustc::mir::{Body, BodyAndCache, Local};
+use rustc::ty::layout::Size;
+use rustc::ty::TyCtxt;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+
+/// The number of locals pointed out by the lint.
+const LARGEST_LOCALS: usize = 3;
+
+pub struct CheckStackFrameSize;
+
+impl<'tcx> MirPass<'tcx> for CheckStackFrameSize {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, source: MirSource<'tcx>, body: &mut BodyAndCache<'tcx>) {
+        // The sizes printed by `-Z print-stack-frame-sizes` are recorded by
+        // `record_stack_frame_sizes` instead, as this pass doesn't run when
+        // the optimized MIR is loaded from the incremental cache.
+        let limit = match tcx.sess.opts.debugging_opts.stack_frame_size_limit {
+            Some(limit) => limit,
+            None => return,
+        };
+        if !has_frame(tcx, source) {
+            return;
+        }
+        let def_id = source.def_id();
+        let (size, locals) = estimate_frame_size(tcx, def_id, body);
+        if size.bytes() <= limit as u64 {
+            return;
+        }
+
+        let hir_id = tcx.hir().as_local_hir_id(def_id).expect("optimizing non-local MIR");
+        let names = local_names(body);
+        let msg = format!(
+            "the stack frame of `{}` is about {} bytes, which exceeds the limit of {} bytes",
+            tcx.def_path_str(def_id),
+            size.bytes(),
+            limit
+        );
+        let span = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+        let mut err = tcx.struct_span_lint_hir(LARGE_STACK_FRAMES, hir_id, span, &msg);
+        for &(local, local_size) in locals.iter().take(LARGEST_LOCALS) {
+            let span = body.local_decls[local].source_info.span;
+            // Inlined locals come from the bodies of other functions.
+            if !body.span.contains(span) {
+                continue;
+            }
+            let label = match names.get(&local) {
+                Some(name) => format!("`{}` takes {} bytes", name, local_size.bytes()),
+                None => format!("this temporary takes {} bytes", local_size.bytes()),
+            };
+            err.span_label(span, label);
+        }
+        err.note(
+            "the size is estimated from the locals of the function, before the optimizations \
+             which may let some of them share their stack slots",
+        );
+        err.emit();
+    }
+}
+
+/// Records the estimated stack frame size of every function of the crate for
+/// `-Z print-stack-frame-sizes`.
+///
+/// This is done once the crate is codegened rather than in the MIR pass, so
+/// that the functions whose optimized MIR is loaded from the incremental
+/// cache are printed as well.
+pub fn record_stack_frame_sizes(tcx: TyCtxt<'_>) {
+    for def_id in tcx.body_owners() {
+        if !has_frame(tcx, MirSource::item(def_id)) {
+            continue;
+        }
+        let body = tcx.optimized_mir(def_id);
+        let (size, locals) = estimate_frame_size(tcx, def_id, body);
+        let names = local_names(body);
+        let report = locals.iter().map(|&(local, size)| (names.get(&local).cloned(), size.bytes()));
+        tcx.sess.code_stats.record_stack_frame_size(
+            tcx.def_path_str(def_id),
+            size,
+            report.collect(),
+        );
+    }
+}
+
+/// Returns whether `source` is run with a stack frame of its own.
+fn has_frame(tcx: TyCtxt<'_>, source: MirSource<'_>) -> bool {
+    // Promoteds, constants and statics are evaluated at compile time.
+    if source.promoted.is_some() {
+        return false;
+    }
+    let def_id = source.def_id();
+    let hir_id = tcx.hir().as_local_hir_id(def_id).expect("optimizing non-local MIR");
+    match tcx.hir().body_owner_kind(hir_id) {
+        hir::BodyOwnerKind::Fn | hir::BodyOwnerKind::Closure => {}
+        hir::BodyOwnerKind::Const | hir::BodyOwnerKind::Static(_) => return false,
+    }
+    // The locals of a generator which are live across a suspension point
+    // are moved to the generator itself, and computing the layout of the
+    // other generators it awaits may cycle back to this one.
+    !tcx.type_of(def_id).is_generator()
+}
+
+/// Estimates the size of the stack frame of the function `def_id`, and
+/// returns it with the sizes of its locals, largest first.
+fn estimate_frame_size<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &Body<'tcx>,
+) -> (Size, Vec<(Local, Size)>) {
+    // The return place and the arguments are counted in the frame of the
+    // caller, which allocates them.
+    let param_env = tcx.param_env(def_id);
+    let mut size = Size::ZERO;
+    let mut locals = Vec::new();
+    for local in body.vars_and_temps_iter() {
+        let decl = &body.local_decls[local];
+        // Layouts which depend on generic parameters are not known until
+        // monomorphization, so these locals are not counted.
+        let layout = match tcx.layout_of(param_env.and(decl.ty)) {
+            Ok(layout) => layout,
+            Err(_) => continue,
+        };
+        if layout.is_zst() {
+            continue;
+        }
+        size = size.align_to(layout.align.abi) + layout.size;
+        locals.push((local, layout.size));
+    }
+    // Largest first, in the order of declaration otherwise.
+    locals.sort_by(|(_, size1), (_, size2)| size2.cmp(size1));
+    (size, locals)
+}
+
+/// Returns the names of the user variables of `body`.
+fn local_names(body: &Body<'_>) -> FxHashMap<Local, String> {
+    body.var_debug_info
+        .iter()
+        .filter(|info| info.place.projection.is_empty())
+        .map(|info| (info.place.local, info.name.to_string()))
+        .collect()
+}
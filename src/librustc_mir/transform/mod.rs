@@ -16,6 +16,7 @@ pub mod add_call_guards;
 pub mod add_moves_for_packed_drops;
 pub mod add_retag;
 pub mod check_consts;
+pub mod check_stack_frame_size;
 pub mod check_unsafety;
 pub mod cleanup_post_borrowck;
 pub mod const_prop;
@@ -320,6 +321,8 @@ fn run_optimization_passes<'tcx>(
             &simplify::SimplifyCfg::new("final"),
             &simplify::SimplifyLocals,
             &add_call_guards::CriticalCallEdges,
+            // Needs the final locals of the function.
+            &check_stack_frame_size::CheckStackFrameSize,
             &dump_mir::Marker("PreCodegen"),
         ],
     );
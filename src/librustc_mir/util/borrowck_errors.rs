@@ -1,10 +1,18 @@
 use rustc::ty::{self, Ty, TyCtxt};
-use rustc_errors::{struct_span_err, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{struct_span_err, DiagnosticBuilder, DiagnosticId, MessageId};
 use rustc_span::{MultiSpan, Span};
 
 impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
     crate fn cannot_move_when_borrowed(&self, span: Span, desc: &str) -> DiagnosticBuilder<'cx> {
-        struct_span_err!(self, span, E0505, "cannot move out of `{}` because it is borrowed", desc,)
+        let mut err = struct_span_err!(
+            self,
+            span,
+            E0505,
+            "cannot move out of `{}` because it is borrowed",
+            desc,
+        );
+        err.set_message_id(MessageId::new("borrowck-move-borrowed").arg("desc", desc));
+        err
     }
 
     crate fn cannot_use_when_mutably_borrowed(
@@ -21,9 +29,18 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             "cannot use `{}` because it was mutably borrowed",
             desc,
         );
+        err.set_message_id(MessageId::new("borrowck-use-mut-borrowed").arg("desc", desc));
 
-        err.span_label(borrow_span, format!("borrow of `{}` occurs here", borrow_desc));
-        err.span_label(span, format!("use of borrowed `{}`", borrow_desc));
+        err.span_label_with_id(
+            borrow_span,
+            format!("borrow of `{}` occurs here", borrow_desc),
+            MessageId::new("borrowck-borrow-here").arg("desc", borrow_desc),
+        );
+        err.span_label_with_id(
+            span,
+            format!("use of borrowed `{}`", borrow_desc),
+            MessageId::new("borrowck-use-borrowed").arg("desc", borrow_desc),
+        );
         err
     }
 
@@ -251,9 +268,18 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             "cannot assign to `{}` because it is borrowed",
             desc,
         );
+        err.set_message_id(MessageId::new("borrowck-assign-borrowed").arg("desc", desc));
 
-        err.span_label(borrow_span, format!("borrow of `{}` occurs here", desc));
-        err.span_label(span, format!("assignment to borrowed `{}` occurs here", desc));
+        err.span_label_with_id(
+            borrow_span,
+            format!("borrow of `{}` occurs here", desc),
+            MessageId::new("borrowck-borrow-here").arg("desc", desc),
+        );
+        err.span_label_with_id(
+            span,
+            format!("assignment to borrowed `{}` occurs here", desc),
+            MessageId::new("borrowck-assign-borrowed-here").arg("desc", desc),
+        );
         err
     }
 
@@ -264,7 +290,11 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
         is_arg: bool,
     ) -> DiagnosticBuilder<'cx> {
         let msg = if is_arg { "to immutable argument" } else { "twice to immutable variable" };
-        struct_span_err!(self, span, E0384, "cannot assign {} `{}`", msg, desc,)
+        let id =
+            if is_arg { "borrowck-assign-immutable-arg" } else { "borrowck-reassign-immutable" };
+        let mut err = struct_span_err!(self, span, E0384, "cannot assign {} `{}`", msg, desc,);
+        err.set_message_id(MessageId::new(id).arg("desc", desc));
+        err
     }
 
     crate fn cannot_assign(&self, span: Span, desc: &str) -> DiagnosticBuilder<'cx> {
@@ -301,7 +331,16 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             ty,
             type_name,
         );
-        err.span_label(move_from_span, "cannot move out of here");
+        let id = match type_name {
+            "array" => "borrowck-move-out-of-array",
+            _ => "borrowck-move-out-of-slice",
+        };
+        err.set_message_id(MessageId::new(id).arg("ty", ty));
+        err.span_label_with_id(
+            move_from_span,
+            "cannot move out of here",
+            MessageId::new("borrowck-move-out-of-here"),
+        );
         err
     }
 
@@ -317,7 +356,12 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
             "cannot move out of type `{}`, which implements the `Drop` trait",
             container_ty,
         );
-        err.span_label(move_from_span, "cannot move out of here");
+        err.set_message_id(MessageId::new("borrowck-move-out-of-drop").arg("ty", container_ty));
+        err.span_label_with_id(
+            move_from_span,
+            "cannot move out of here",
+            MessageId::new("borrowck-move-out-of-here"),
+        );
         err
     }
 
@@ -457,7 +501,10 @@ impl<'cx, 'tcx> crate::borrow_check::MirBorrowckCtxt<'cx, 'tcx> {
     }
 
     crate fn temporary_value_borrowed_for_too_long(&self, span: Span) -> DiagnosticBuilder<'cx> {
-        struct_span_err!(self, span, E0716, "temporary value dropped while borrowed",)
+        let mut err =
+            struct_span_err!(self, span, E0716, "temporary value dropped while borrowed",);
+        err.set_message_id(MessageId::new("borrowck-temporary-dropped-while-borrowed"));
+        err
     }
 
     fn struct_span_err_with_code<S: Into<MultiSpan>>(
@@ -67,6 +67,10 @@ impl CheckAttrVisitor<'tcx> {
                 self.check_target_feature(attr, span, target)
             } else if attr.check_name(sym::track_caller) {
                 self.check_track_caller(&attr.span, attrs, span, target)
+            } else if attr.check_name(sym::must_not_suspend)
+                || attr.check_name(sym::has_significant_drop)
+            {
+                self.check_type_attribute(attr, span, target)
             } else {
                 true
             };
@@ -217,6 +221,25 @@ impl CheckAttrVisitor<'tcx> {
         }
     }
 
+    /// Checks if an attribute which only applies to types, like `#[must_not_suspend]`, is
+    /// valid. Returns `true` if valid.
+    fn check_type_attribute(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Struct | Target::Enum | Target::Union => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "attribute should be applied to a struct, enum, or union",
+                    )
+                    .span_label(*span, "not a struct, enum, or union")
+                    .emit();
+                false
+            }
+        }
+    }
+
     /// Checks if the `#[target_feature]` attribute on `item` is valid. Returns `true` if valid.
     fn check_target_feature(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
         match target {
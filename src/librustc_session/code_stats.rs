@@ -45,9 +45,19 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct StackFrameSizeInfo {
+    pub fn_description: String,
+    pub size: u64,
+    /// The name (`None` for temporaries) and size of the locals of the
+    /// function, largest first.
+    pub locals: Vec<(Option<String>, u64)>,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    stack_frame_sizes: Lock<FxHashSet<StackFrameSizeInfo>>,
 }
 
 impl CodeStats {
@@ -190,4 +200,38 @@ impl CodeStats {
             }
         }
     }
+
+    pub fn record_stack_frame_size<S: ToString>(
+        &self,
+        fn_desc: S,
+        size: Size,
+        locals: Vec<(Option<String>, u64)>,
+    ) {
+        let info =
+            StackFrameSizeInfo { fn_description: fn_desc.to_string(), size: size.bytes(), locals };
+        self.stack_frame_sizes.borrow_mut().insert(info);
+    }
+
+    pub fn print_stack_frame_sizes(&self) {
+        let stack_frame_sizes = self.stack_frame_sizes.borrow();
+        let mut sorted: Vec<_> = stack_frame_sizes.iter().collect();
+
+        // Large-to-small, then by description, like for the type sizes.
+        sorted.sort_by(|info1, info2| match info2.size.cmp(&info1.size) {
+            Ordering::Equal => info1.fn_description.cmp(&info2.fn_description),
+            other => other,
+        });
+
+        for info in &sorted {
+            println!("print-stack-frame-size fn `{}`: {} bytes", info.fn_description, info.size);
+            for (name, size) in &info.locals {
+                match name {
+                    Some(name) => {
+                        println!("print-stack-frame-size     local `{}`: {} bytes", name, size)
+                    }
+                    None => println!("print-stack-frame-size     temporary: {} bytes", size),
+                }
+            }
+        }
+    }
 }
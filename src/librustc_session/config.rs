@@ -26,7 +26,9 @@ use std::collections::btree_map::{
     Iter as BTreeMapIter, Keys as BTreeMapKeysIter, Values as BTreeMapValuesIter,
 };
 use std::collections::{BTreeMap, BTreeSet};
+use std::env;
 use std::fmt;
+use std::fs;
 use std::iter::{self, FromIterator};
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
@@ -268,6 +270,9 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log written at the end of the compilation, which is
+    /// consumed by code scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -548,6 +553,7 @@ impl Default for Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: Vec::new(),
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -592,7 +598,20 @@ impl Options {
     }
 
     pub fn file_path_mapping(&self) -> FilePathMapping {
-        FilePathMapping::new(self.remap_path_prefix.clone())
+        let mapping = FilePathMapping::new(self.remap_path_prefix.clone());
+        // Incremental compilation identifies the source files by their path
+        // relative to the root of the workspace, so that its cache can be
+        // moved along with the workspace. The working directory is taken as
+        // the root, which is where Cargo runs the compiler. Other compilations
+        // identify the files by their path as given, as the hashes of the
+        // spans in them are part of the SVH.
+        if self.incremental.is_none() {
+            return mapping;
+        }
+        match env::current_dir() {
+            Ok(dir) => mapping.with_workspace_root(dir),
+            Err(_) => mapping,
+        }
     }
 
     /// Returns `true` if there will be an output file generated.
@@ -626,6 +645,10 @@ impl DebuggingOptions {
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics.unwrap_or(true),
+            apply_suggestions: self.apply_suggestions,
+            max_errors: self.max_errors,
+            max_errors_per_code: self.max_errors_per_code,
+            error_summary: self.error_summary,
         }
     }
 }
@@ -997,6 +1020,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt("", "lint-config", "Set lint levels from a configuration file", "PATH"),
     ]);
     opts
 }
@@ -1025,6 +1049,76 @@ pub fn get_cmd_lint_options(
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// A lint level set by the lint configuration file (see `--lint-config`).
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct LintConfigEntry {
+    /// The name of the lint or lint group.
+    pub name: String,
+    pub level: lint::Level,
+    /// The path prefix of the files the level is set for, or `None` if it is
+    /// set for the whole crate.
+    pub scope: Option<PathBuf>,
+}
+
+/// Parses the lint configuration file given with `--lint-config`.
+///
+/// Every line of the file sets the level of a lint or lint group, optionally
+/// only for the modules loaded from the files whose path starts with a given
+/// prefix, e.g. `allow dead_code src/generated/`. Relative prefixes are relative
+/// to the directory of the file. Empty lines and lines starting with `#` are
+/// ignored. Later lines take precedence over earlier ones.
+pub fn parse_lint_config(
+    matches: &getopts::Matches,
+    error_format: ErrorOutputType,
+) -> Vec<LintConfigEntry> {
+    let path = match matches.opt_str("lint-config") {
+        Some(path) => PathBuf::from(path),
+        None => return Vec::new(),
+    };
+    let contents = fs::read_to_string(&path).unwrap_or_else(|e| {
+        early_error(
+            error_format,
+            &format!("failed to read the lint configuration file `{}`: {}", path.display(), e),
+        )
+    });
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let dir = env::current_dir().map(|cwd| cwd.join(dir)).unwrap_or_else(|_| dir.to_path_buf());
+
+    let mut entries = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let location = format!("{}:{}", path.display(), i + 1);
+        let mut fields = line.split_whitespace();
+        let level = fields.next().unwrap();
+        let level = lint::Level::from_str(level).unwrap_or_else(|| {
+            early_error(error_format, &format!("{}: unknown lint level: `{}`", location, level))
+        });
+        let name = match fields.next() {
+            Some(name) => name.replace("-", "_"),
+            None => early_error(
+                error_format,
+                &format!("{}: expected the name of a lint after the level", location),
+            ),
+        };
+        let scope = fields.next().map(|prefix| dir.join(prefix));
+        if fields.next().is_some() {
+            early_error(
+                error_format,
+                &format!(
+                    "{}: expected a lint level, a lint name and an optional path prefix",
+                    location
+                ),
+            );
+        }
+        entries.push(LintConfigEntry { name, level, scope });
+    }
+    entries
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_ref().map(|s| &s[..]) {
@@ -1100,13 +1194,14 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{}`)",
+                    "argument for `--error-format` must be `human`, `json`, \
+                     `short` or `sarif` (instead was `{}`)",
                     arg
                 ),
             ),
@@ -1183,6 +1278,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -1653,6 +1754,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         .unwrap_or_else(|e| early_error(error_format, &e[..]));
 
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
+    let lint_config = parse_lint_config(matches, error_format);
 
     let mut debugging_opts = build_debugging_options(matches, error_format);
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
@@ -1727,6 +1829,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -1989,8 +2092,8 @@ impl PpMode {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
-        OutputTypes, Passes, Sanitizer, SwitchWithOptPath, SymbolManglingVersion,
+        CFGuard, CrateType, DebugInfo, ErrorOutputType, LintConfigEntry, LinkerPluginLto, LtoCli,
+        OptLevel, OutputTypes, Passes, Sanitizer, SwitchWithOptPath, SymbolManglingVersion,
     };
     use crate::lint;
     use crate::utils::NativeLibraryKind;
@@ -2068,6 +2171,8 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
     impl_dep_tracking_hash_via_hash!(SwitchWithOptPath);
     impl_dep_tracking_hash_via_hash!(SymbolManglingVersion);
+    // The order of the entries matters, as later ones take precedence.
+    impl_dep_tracking_hash_via_hash!(Vec<LintConfigEntry>);
 
     impl_dep_tracking_hash_for_sortable_vec_of!(String);
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
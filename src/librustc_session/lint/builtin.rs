@@ -65,6 +65,12 @@ declare_lint! {
     "extern crates that are never used"
 }
 
+declare_lint! {
+    pub UNUSED_CRATE_DEPENDENCIES,
+    Allow,
+    "crate dependencies that are never used"
+}
+
 declare_lint! {
     pub UNUSED_QUALIFICATIONS,
     Allow,
@@ -490,6 +496,18 @@ declare_lint! {
     "detects incompatible use of `#[inline(always)]` and `#[no_sanitize(...)]`",
 }
 
+declare_lint! {
+    pub MUST_NOT_SUSPEND,
+    Warn,
+    "detects values of `#[must_not_suspend]` types held across suspension points"
+}
+
+declare_lint! {
+    pub LARGE_STACK_FRAMES,
+    Warn,
+    "detects functions whose stack frame exceeds `-Z stack-frame-size-limit`"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -498,6 +516,7 @@ declare_lint_pass! {
         EXCEEDING_BITSHIFTS,
         UNUSED_IMPORTS,
         UNUSED_EXTERN_CRATES,
+        UNUSED_CRATE_DEPENDENCIES,
         UNUSED_QUALIFICATIONS,
         UNKNOWN_LINTS,
         UNUSED_VARIABLES,
@@ -555,5 +574,7 @@ declare_lint_pass! {
         INDIRECT_STRUCTURAL_MATCH,
         SOFT_UNSTABLE,
         INLINE_NO_SANITIZE,
+        MUST_NOT_SUSPEND,
+        LARGE_STACK_FRAMES,
     ]
 }
@@ -89,6 +89,7 @@ top_level_options!(
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED],
         lint_cap: Option<lint::Level> [TRACKED],
+        lint_config: Vec<LintConfigEntry> [TRACKED],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -724,6 +725,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "select which borrowck is used (`mir` or `migrate`)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each rustc pass"),
+    time_passes_json: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the time and memory usage of each rustc pass to a file, as JSON lines \
+         (the LLVM passes timed by `-Z time-llvm-passes` are not included)"),
     time: bool = (false, parse_bool, [UNTRACKED],
         "measure time of rustc processes"),
     time_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
@@ -962,6 +966,32 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
          (such as entering an empty infinite loop) by inserting llvm.sideeffect"),
     deduplicate_diagnostics: Option<bool> = (None, parse_opt_bool, [UNTRACKED],
         "deduplicate identical diagnostics"),
+    diagnostic_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "suppress the warnings and lints listed in the given baseline file (they are \
+         identified by their code, item and message), and list the suppressed ones in \
+         `<file>.suppressed`"),
+    write_diagnostic_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the warnings and lints emitted by the compilation to a baseline file"),
+    apply_suggestions: bool = (false, parse_bool, [UNTRACKED],
+        "apply the machine applicable suggestions to the source code"),
+    translation_lang: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "translate the diagnostics to this language (e.g. `fr`), if they can be"),
+    translation_dir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "the directory of the translation catalogs (default: `SYSROOT/share/locale`)"),
+    stack_frame_size_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "lint the functions whose stack frame exceeds this number of bytes"),
+    print_stack_frame_sizes: bool = (false, parse_bool, [UNTRACKED],
+        "print the estimated stack frame size of each function"),
+    terminal_hyperlinks: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "link the locations of the diagnostics printed to a terminal with this URI template \
+         (`file`, `vscode`, or a template in which `{path}`, `{line}` and `{col}` are replaced)"),
+    max_errors: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "show at most this many errors, and summarize the others by error code and module"),
+    max_errors_per_code: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "show at most this many errors with each error code, and summarize the others by \
+         module"),
+    error_summary: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of errors with each error code and in each module at the end"),
     control_flow_guard: CFGuard = (CFGuard::Disabled, parse_cfguard, [UNTRACKED],
         "use Windows Control Flow Guard (`disabled`, `nochecks` or `checks`)"),
     no_link: bool = (false, parse_bool, [TRACKED],
@@ -20,9 +20,12 @@ use rustc_data_structures::sync::{
 
 use crate::parse::ParseSess;
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
+use rustc_errors::baseline::DiagnosticBaseline;
 use rustc_errors::emitter::HumanReadableErrorType;
-use rustc_errors::emitter::{Emitter, EmitterWriter};
+use rustc_errors::emitter::{ColorConfig, Emitter, EmitterWriter, HyperlinkTemplate};
 use rustc_errors::json::JsonEmitter;
+use rustc_errors::sarif::SarifEmitter;
+use rustc_errors::translation::Catalog;
 use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId};
 use rustc_span::edition::Edition;
 use rustc_span::source_map;
@@ -30,7 +33,7 @@ use rustc_span::{MultiSpan, Span};
 
 use rustc_data_structures::flock;
 use rustc_data_structures::jobserver::{self, Client};
-use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef};
+use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef, TimePassesReport};
 use rustc_target::spec::{PanicStrategy, RelroLevel, Target, TargetTriple};
 
 use std::cell::{self, RefCell};
@@ -859,6 +862,7 @@ fn default_emitter(
     emitter_dest: Option<Box<dyn Write + Send>>,
 ) -> Box<dyn Emitter + sync::Send> {
     let macro_backtrace = sopts.debugging_opts.macro_backtrace;
+    let catalog = load_catalog(sopts);
     match (sopts.error_format, emitter_dest) {
         (config::ErrorOutputType::HumanReadable(kind), dst) => {
             let (short, color_config) = kind.unzip();
@@ -869,7 +873,9 @@ fn default_emitter(
                     short,
                     macro_backtrace,
                 );
-                Box::new(emitter.ui_testing(sopts.debugging_opts.ui_testing()))
+                Box::new(
+                    emitter.ui_testing(sopts.debugging_opts.ui_testing()).with_catalog(catalog),
+                )
             } else {
                 let emitter = match dst {
                     None => EmitterWriter::stderr(
@@ -890,7 +896,12 @@ fn default_emitter(
                         macro_backtrace,
                     ),
                 };
-                Box::new(emitter.ui_testing(sopts.debugging_opts.ui_testing()))
+                Box::new(
+                    emitter
+                        .ui_testing(sopts.debugging_opts.ui_testing())
+                        .with_catalog(catalog)
+                        .with_hyperlinks(hyperlink_template(sopts)),
+                )
             }
         }
         (config::ErrorOutputType::Json { pretty, json_rendered }, None) => Box::new(
@@ -901,7 +912,8 @@ fn default_emitter(
                 json_rendered,
                 macro_backtrace,
             )
-            .ui_testing(sopts.debugging_opts.ui_testing()),
+            .ui_testing(sopts.debugging_opts.ui_testing())
+            .with_catalog(catalog),
         ),
         (config::ErrorOutputType::Json { pretty, json_rendered }, Some(dst)) => Box::new(
             JsonEmitter::new(
@@ -912,7 +924,51 @@ fn default_emitter(
                 json_rendered,
                 macro_backtrace,
             )
-            .ui_testing(sopts.debugging_opts.ui_testing()),
+            .ui_testing(sopts.debugging_opts.ui_testing())
+            .with_catalog(catalog),
+        ),
+        (config::ErrorOutputType::Sarif, None) => {
+            Box::new(SarifEmitter::stderr(source_map.clone()).with_catalog(catalog))
+        }
+        (config::ErrorOutputType::Sarif, Some(dst)) => {
+            Box::new(SarifEmitter::new(dst, source_map.clone()).with_catalog(catalog))
+        }
+    }
+}
+
+/// Parses the template of the hyperlinks requested with `-Z terminal-hyperlinks`,
+/// if any.
+fn hyperlink_template(sopts: &config::Options) -> Option<HyperlinkTemplate> {
+    let template = sopts.debugging_opts.terminal_hyperlinks.as_ref()?;
+    match HyperlinkTemplate::parse(template) {
+        Ok(template) => Some(template),
+        Err(e) => early_error(sopts.error_format, &e),
+    }
+}
+
+/// Loads the translation catalog requested with `-Z translation-lang`, if any.
+fn load_catalog(sopts: &config::Options) -> Option<Lrc<Catalog>> {
+    let lang = sopts.debugging_opts.translation_lang.as_ref()?;
+    let dir = match &sopts.debugging_opts.translation_dir {
+        Some(dir) => dir.clone(),
+        None => {
+            let sysroot = match &sopts.maybe_sysroot {
+                Some(sysroot) => sysroot.clone(),
+                None => filesearch::get_or_default_sysroot(),
+            };
+            sysroot.join("share").join("locale")
+        }
+    };
+    match Catalog::load(&dir, lang) {
+        Ok(catalog) => Some(Lrc::new(catalog)),
+        Err(e) => early_error(
+            sopts.error_format,
+            &format!(
+                "failed to load the `{}` translation catalog from `{}`: {}",
+                lang,
+                dir.display(),
+                e
+            ),
         ),
     }
 }
@@ -954,6 +1010,21 @@ pub fn build_session_with_source_map(
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
 
+    let baseline_input = &sopts.debugging_opts.diagnostic_baseline;
+    let baseline_output = &sopts.debugging_opts.write_diagnostic_baseline;
+    if baseline_input.is_some() || baseline_output.is_some() {
+        match DiagnosticBaseline::new(baseline_input.clone(), baseline_output.clone()) {
+            Ok(baseline) => diagnostic_handler.set_diagnostic_baseline(baseline),
+            Err(e) => {
+                let path = baseline_input.as_ref().unwrap().display();
+                early_error(
+                    sopts.error_format,
+                    &format!("failed to read the diagnostic baseline `{}`: {}", path, e),
+                )
+            }
+        }
+    }
+
     build_session_(sopts, local_crate_source_file, diagnostic_handler, source_map, lint_caps)
 }
 
@@ -985,6 +1056,21 @@ fn build_session_(
         None
     };
 
+    let time_passes_report = if let Some(ref path) = sopts.debugging_opts.time_passes_json {
+        match TimePassesReport::new(path) {
+            Ok(report) => Some(Arc::new(report)),
+            Err(e) => {
+                early_warn(
+                    sopts.error_format,
+                    &format!("failed to create time passes report {}: {}", path.display(), e),
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     let host_triple = TargetTriple::from_triple(config::host_triple());
     let host = Target::search(&host_triple).unwrap_or_else(|e| {
         span_diagnostic.fatal(&format!("Error loading host specification: {}", e)).raise()
@@ -1034,6 +1120,7 @@ fn build_session_(
         self_profiler,
         sopts.debugging_opts.time_passes || sopts.debugging_opts.time,
         sopts.debugging_opts.time_passes,
+        time_passes_report,
     );
 
     let sess = Session {
@@ -1215,6 +1302,11 @@ pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, false))
         }
+        // A SARIF log is a single document written when the emitter is dropped,
+        // so early diagnostics, of which there may be several, are plain text.
+        config::ErrorOutputType::Sarif => {
+            Box::new(EmitterWriter::stderr(ColorConfig::Never, None, false, false, None, false))
+        }
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1230,6 +1322,10 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, false))
         }
+        // See `early_error`.
+        config::ErrorOutputType::Sarif => {
+            Box::new(EmitterWriter::stderr(ColorConfig::Never, None, false, false, None, false))
+        }
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
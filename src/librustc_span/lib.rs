@@ -922,7 +922,9 @@ pub struct SourceFile {
     pub non_narrow_chars: Vec<NonNarrowChar>,
     /// Locations of characters removed during normalization.
     pub normalized_pos: Vec<NormalizedPos>,
-    /// A hash of the filename, used for speeding up hashing in incremental compilation.
+    /// A hash of the filename relative to the workspace root (see
+    /// `FilePathMapping::stable_name`), used for speeding up hashing in
+    /// incremental compilation.
     pub name_hash: u128,
 }
 
@@ -1074,6 +1076,7 @@ impl SourceFile {
         name: FileName,
         name_was_remapped: bool,
         unmapped_path: FileName,
+        stable_name: &FileName,
         mut src: String,
         start_pos: BytePos,
     ) -> Result<SourceFile, OffsetOverflowError> {
@@ -1086,7 +1089,7 @@ impl SourceFile {
         };
         let name_hash = {
             let mut hasher: StableHasher = StableHasher::new();
-            name.hash(&mut hasher);
+            stable_name.hash(&mut hasher);
             hasher.finish::<u128>()
         };
         let end_pos = start_pos.to_usize() + src.len();
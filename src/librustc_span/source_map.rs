@@ -92,16 +92,18 @@ impl FileLoader for RealFileLoader {
 
 // This is a `SourceFile` identifier that is used to correlate `SourceFile`s between
 // subsequent compilation sessions (which is something we need to do during
-// incremental compilation).
+// incremental compilation). The paths are hashed relative to the workspace root,
+// so that the identifiers stay the same when the whole workspace is moved.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable, Debug)]
 pub struct StableSourceFileId(u128);
 
 impl StableSourceFileId {
-    pub fn new(source_file: &SourceFile) -> StableSourceFileId {
+    pub fn new(source_file: &SourceFile, path_mapping: &FilePathMapping) -> StableSourceFileId {
         StableSourceFileId::new_from_pieces(
             &source_file.name,
             source_file.name_was_remapped,
             source_file.unmapped_path.as_ref(),
+            path_mapping,
         )
     }
 
@@ -109,12 +111,13 @@ impl StableSourceFileId {
         name: &FileName,
         name_was_remapped: bool,
         unmapped_path: Option<&FileName>,
+        path_mapping: &FilePathMapping,
     ) -> StableSourceFileId {
         let mut hasher = StableHasher::new();
 
-        name.hash(&mut hasher);
+        path_mapping.stable_name(name).hash(&mut hasher);
         name_was_remapped.hash(&mut hasher);
-        unmapped_path.hash(&mut hasher);
+        unmapped_path.map(|path| path_mapping.stable_name(path)).hash(&mut hasher);
 
         StableSourceFileId(hasher.finish())
     }
@@ -235,16 +238,22 @@ impl SourceMap {
             other => (other, false),
         };
 
-        let file_id =
-            StableSourceFileId::new_from_pieces(&filename, was_remapped, Some(&unmapped_path));
+        let file_id = StableSourceFileId::new_from_pieces(
+            &filename,
+            was_remapped,
+            Some(&unmapped_path),
+            &self.path_mapping,
+        );
 
         let lrc_sf = match self.source_file_by_stable_id(file_id) {
             Some(lrc_sf) => lrc_sf,
             None => {
+                let stable_name = self.path_mapping.stable_name(&filename);
                 let source_file = Lrc::new(SourceFile::new(
                     filename,
                     was_remapped,
                     unmapped_path,
+                    &stable_name,
                     src,
                     Pos::from_usize(start_pos),
                 )?);
@@ -320,7 +329,7 @@ impl SourceMap {
         files.source_files.push(source_file.clone());
         files
             .stable_id_to_source_file
-            .insert(StableSourceFileId::new(&source_file), source_file.clone());
+            .insert(StableSourceFileId::new(&source_file, &self.path_mapping), source_file.clone());
 
         source_file
     }
@@ -950,15 +959,42 @@ impl SourceMap {
 #[derive(Clone)]
 pub struct FilePathMapping {
     mapping: Vec<(PathBuf, PathBuf)>,
+    /// The directory the paths of the source files are hashed relative to, if any.
+    workspace_root: Option<PathBuf>,
 }
 
 impl FilePathMapping {
     pub fn empty() -> FilePathMapping {
-        FilePathMapping { mapping: vec![] }
+        FilePathMapping { mapping: vec![], workspace_root: None }
     }
 
     pub fn new(mapping: Vec<(PathBuf, PathBuf)>) -> FilePathMapping {
-        FilePathMapping { mapping }
+        FilePathMapping { mapping, workspace_root: None }
+    }
+
+    /// Hashes the paths of the source files in `root` relative to it, so that
+    /// the fingerprints of incremental compilation stay the same when the
+    /// whole workspace is moved to another directory.
+    pub fn with_workspace_root(self, root: PathBuf) -> FilePathMapping {
+        FilePathMapping { workspace_root: Some(root), ..self }
+    }
+
+    pub fn workspace_root(&self) -> Option<&Path> {
+        self.workspace_root.as_deref()
+    }
+
+    /// Returns the name a source file is identified by across compilation
+    /// sessions: its path relative to the workspace root, if it is in it.
+    /// Remapped paths are already independent of the workspace, and so are
+    /// returned as they are.
+    pub fn stable_name(&self, name: &FileName) -> FileName {
+        match (name, &self.workspace_root) {
+            (FileName::Real(path), Some(root)) => match path.strip_prefix(root) {
+                Ok(rest) => FileName::Real(rest.to_path_buf()),
+                Err(_) => name.clone(),
+            },
+            _ => name.clone(),
+        }
     }
 
     /// Applies any path prefix substitution as defined by the mapping.
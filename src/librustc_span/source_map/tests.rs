@@ -168,6 +168,34 @@ fn span_merging_fail() {
     assert!(sm.merge_spans(span1, span2).is_none());
 }
 
+/// Tests that source files are identified the same way when the workspace is moved.
+#[test]
+fn stable_names_relative_to_workspace_root() {
+    let source_file = |root: &str, path: &str| {
+        let sm = SourceMap::new(FilePathMapping::empty().with_workspace_root(root.into()));
+        let sf = sm.new_source_file(PathBuf::from(path).into(), "fn main() {}\n".to_string());
+        (sf.name_hash, StableSourceFileId::new(&sf, sm.path_mapping()))
+    };
+
+    assert_eq!(source_file("/a", "/a/src/main.rs"), source_file("/b", "/b/src/main.rs"));
+    assert_eq!(source_file("/a", "/a/src/main.rs"), source_file("/b", "src/main.rs"));
+    assert_ne!(source_file("/a", "/a/src/main.rs"), source_file("/b", "/a/src/main.rs"));
+}
+
+/// Tests that remapped paths are identified as they are.
+#[test]
+fn stable_names_of_remapped_paths() {
+    let source_file = |root: &str| {
+        let mapping = FilePathMapping::new(vec![(root.into(), "/src".into())]);
+        let sm = SourceMap::new(mapping.with_workspace_root(root.into()));
+        let sf = sm.new_source_file(Path::new(root).join("lib.rs").into(), String::new());
+        assert_eq!(sf.name, PathBuf::from("/src/lib.rs").into());
+        (sf.name_hash, StableSourceFileId::new(&sf, sm.path_mapping()))
+    };
+
+    assert_eq!(source_file("/a"), source_file("/b"));
+}
+
 /// Returns the span corresponding to the `n`th occurrence of `substring` in `source_text`.
 trait SourceMapExtension {
     fn span_substr(
@@ -145,6 +145,8 @@ symbols! {
         Arguments,
         ArgumentV1,
         arm_target_feature,
+        as_mut_ptr,
+        as_ptr,
         asm,
         assert,
         associated_consts,
@@ -154,6 +156,7 @@ symbols! {
         assume_init,
         async_await,
         async_closure,
+        async_test,
         attr,
         attributes,
         attr_literals,
@@ -236,6 +239,7 @@ symbols! {
         crate_name,
         crate_type,
         crate_visibility_modifier,
+        cstring_type,
         ctpop,
         cttz,
         cttz_nonzero,
@@ -283,6 +287,7 @@ symbols! {
         dropck_eyepatch,
         dropck_parametricity,
         drop_types_in_const,
+        Duration,
         dylib,
         dyn_trait,
         eh_personality,
@@ -332,6 +337,7 @@ symbols! {
         from_generator,
         from_method,
         from_ok,
+        from_secs,
         from_usize,
         fundamental,
         future,
@@ -347,6 +353,7 @@ symbols! {
         global_asm,
         globs,
         half_open_range_patterns,
+        has_significant_drop,
         hash,
         Hash,
         HashSet,
@@ -460,6 +467,7 @@ symbols! {
         move_val_init,
         movbe_target_feature,
         mul_with_overflow,
+        must_not_suspend,
         must_use,
         naked,
         naked_functions,
@@ -697,6 +705,7 @@ symbols! {
         std,
         std_inject,
         str,
+        string_type,
         stringify,
         stmt,
         stmt_expr_attributes,
@@ -723,9 +732,11 @@ symbols! {
         test_case,
         test_removed_feature,
         test_runner,
+        test_timeout,
         then_with,
         thread,
         thread_local,
+        time,
         tool_attributes,
         tool_lints,
         trace_macros,
@@ -790,6 +801,7 @@ symbols! {
         val,
         var,
         vec,
+        vec_type,
         Vec,
         vis,
         visible_private_types,
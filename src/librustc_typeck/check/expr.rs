@@ -27,6 +27,7 @@ use rustc::ty::Ty;
 use rustc::ty::TypeFoldable;
 use rustc::ty::{AdtKind, Visibility};
 use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::MessageId;
 use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, DiagnosticId};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
@@ -349,6 +350,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                             "type `{}` cannot be dereferenced",
                             oprnd_t,
                         );
+                        err.set_message_id(
+                            MessageId::new("typeck-cannot-deref").arg("ty", oprnd_t),
+                        );
                         let sp = tcx.sess.source_map().start_point(expr.span);
                         if let Some(sp) =
                             tcx.sess.parse_sess.ambiguous_block_expr_parse.borrow().get(&sp)
@@ -1515,6 +1519,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 "`{}` is a primitive type and therefore doesn't have fields",
                 expr_t
             )
+            .set_message_id(MessageId::new("typeck-primitive-no-fields").arg("ty", expr_t))
             .emit();
         }
 
@@ -1715,7 +1720,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         field: T,
         expr_t: &ty::TyS<'_>,
     ) -> DiagnosticBuilder<'_> {
-        type_error_struct!(
+        let mut err = type_error_struct!(
             self.tcx().sess,
             span,
             expr_t,
@@ -1723,7 +1728,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             "no field `{}` on type `{}`",
             field,
             expr_t
-        )
+        );
+        err.set_message_id(MessageId::new("typeck-no-field").arg("field", field).arg("ty", expr_t));
+        err
     }
 
     fn check_expr_index(
@@ -1757,6 +1764,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                         "cannot index into a value of type `{}`",
                         base_t
                     );
+                    err.set_message_id(MessageId::new("typeck-cannot-index").arg("ty", base_t));
                     // Try to give some advice about indexing tuples.
                     if let ty::Tuple(..) = base_t.kind {
                         let mut needs_note = true;
@@ -4,6 +4,7 @@
 //! types computed here.
 
 use super::FnCtxt;
+use crate::lint;
 use rustc::hir::map::Map;
 use rustc::middle::region::{self, YieldData};
 use rustc::ty::{self, Ty};
@@ -12,7 +13,8 @@ use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
-use rustc_hir::{Expr, ExprKind, Pat, PatKind};
+use rustc_hir::{Expr, ExprKind, HirId, Pat, PatKind};
+use rustc_span::symbol::sym;
 use rustc_span::Span;
 
 struct InteriorVisitor<'a, 'tcx> {
@@ -22,12 +24,15 @@ struct InteriorVisitor<'a, 'tcx> {
     expr_count: usize,
     kind: hir::GeneratorKind,
     prev_unresolved_span: Option<Span>,
+    /// The values already reported by the `must_not_suspend` lint.
+    linted_values: FxHashSet<HirId>,
 }
 
 impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
     fn record(
         &mut self,
         ty: Ty<'tcx>,
+        hir_id: HirId,
         scope: Option<region::Scope>,
         expr: Option<&'tcx Expr<'tcx>>,
         source_span: Span,
@@ -89,6 +94,15 @@ impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
                     .span_note(yield_data.span, &*note)
                     .emit();
             } else {
+                // Bindings and temporaries are the values which are actually held
+                // across the yield, not the places they are used through.
+                let is_place = expr.map_or(false, |e| e.is_syntactic_place_expr());
+                if scope.is_some() && !is_place && !self.linted_values.contains(&hir_id) {
+                    if self.check_must_not_suspend_ty(ty, hir_id, source_span, &yield_data) {
+                        self.linted_values.insert(hir_id);
+                    }
+                }
+
                 // Map the type to the number of types added before it
                 let entries = self.types.len();
                 let scope_span = scope.map(|s| s.span(self.fcx.tcx, self.region_scope_tree));
@@ -120,6 +134,59 @@ impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
             }
         }
     }
+
+    /// Reports a value held across a yield if its type is `#[must_not_suspend]`,
+    /// or is a tuple, array or box holding such a type. Returns whether the
+    /// value was reported.
+    fn check_must_not_suspend_ty(
+        &self,
+        ty: Ty<'tcx>,
+        hir_id: HirId,
+        source_span: Span,
+        yield_data: &YieldData,
+    ) -> bool {
+        let tcx = self.fcx.tcx;
+        match ty.kind {
+            ty::Adt(..) if ty.is_box() => {
+                self.check_must_not_suspend_ty(ty.boxed_ty(), hir_id, source_span, yield_data)
+            }
+            ty::Adt(def, _) => {
+                let attrs = tcx.get_attrs(def.did);
+                let attr = match attrs.iter().find(|attr| attr.check_name(sym::must_not_suspend)) {
+                    Some(attr) => attr,
+                    None => return false,
+                };
+                let msg = format!(
+                    "`{}` held across a suspend point, but should not be",
+                    tcx.def_path_str(def.did)
+                );
+                let mut err = tcx.struct_span_lint_hir(
+                    lint::builtin::MUST_NOT_SUSPEND,
+                    hir_id,
+                    source_span,
+                    &msg,
+                );
+                err.span_label(
+                    yield_data.span,
+                    format!("the value is held across this {}", yield_data.source),
+                );
+                if let Some(reason) = attr.value_str() {
+                    err.note(&reason.as_str());
+                }
+                err.help(
+                    "consider using a block (`{ ... }`) to shrink the value's scope, \
+                     ending before the suspend point",
+                );
+                err.emit();
+                true
+            }
+            ty::Tuple(..) => ty
+                .tuple_fields()
+                .any(|ty| self.check_must_not_suspend_ty(ty, hir_id, source_span, yield_data)),
+            ty::Array(ty, _) => self.check_must_not_suspend_ty(ty, hir_id, source_span, yield_data),
+            _ => false,
+        }
+    }
 }
 
 pub fn resolve_interior<'a, 'tcx>(
@@ -137,6 +204,7 @@ pub fn resolve_interior<'a, 'tcx>(
         expr_count: 0,
         kind,
         prev_unresolved_span: None,
+        linted_values: FxHashSet::default(),
     };
     intravisit::walk_body(&mut visitor, body);
 
@@ -223,7 +291,7 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         if let PatKind::Binding(..) = pat.kind {
             let scope = self.region_scope_tree.var_scope(pat.hir_id.local_id);
             let ty = self.fcx.tables.borrow().pat_ty(pat);
-            self.record(ty, Some(scope), None, pat.span);
+            self.record(ty, pat.hir_id, Some(scope), None, pat.span);
         }
     }
 
@@ -263,7 +331,7 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         // If there are adjustments, then record the final type --
         // this is the actual value that is being produced.
         if let Some(adjusted_ty) = self.fcx.tables.borrow().expr_ty_adjusted_opt(expr) {
-            self.record(adjusted_ty, scope, Some(expr), expr.span);
+            self.record(adjusted_ty, expr.hir_id, scope, Some(expr), expr.span);
         }
 
         // Also record the unadjusted type (which is the only type if
@@ -291,7 +359,7 @@ impl<'a, 'tcx> Visitor<'tcx> for InteriorVisitor<'a, 'tcx> {
         // The type table might not have information for this expression
         // if it is in a malformed scope. (#66387)
         if let Some(ty) = self.fcx.tables.borrow().expr_ty_opt(expr) {
-            self.record(ty, scope, Some(expr), expr.span);
+            self.record(ty, expr.hir_id, scope, Some(expr), expr.span);
         } else {
             self.fcx.tcx.sess.delay_span_bug(expr.span, "no type for node");
         }
@@ -6,7 +6,7 @@ use rustc::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc::ty::adjustment::{Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability};
 use rustc::ty::TyKind::{Adt, Array, Char, FnDef, Never, Ref, Str, Tuple, Uint};
 use rustc::ty::{self, Ty, TypeFoldable};
-use rustc_errors::{self, struct_span_err, Applicability};
+use rustc_errors::{self, struct_span_err, Applicability, MessageId};
 use rustc_hir as hir;
 use rustc_span::Span;
 use syntax::ast::Ident;
@@ -251,9 +251,17 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                 op.node.as_str(),
                                 lhs_ty,
                             );
-                            err.span_label(
+                            err.set_message_id(
+                                MessageId::new("typeck-assign-op-unsupported")
+                                    .arg("op", op.node.as_str())
+                                    .arg("ty", lhs_ty),
+                            );
+                            err.span_label_with_id(
                                 lhs_expr.span,
                                 format!("cannot use `{}=` on type `{}`", op.node.as_str(), lhs_ty),
+                                MessageId::new("typeck-assign-op-unsupported-label")
+                                    .arg("op", op.node.as_str())
+                                    .arg("ty", lhs_ty),
                             );
                             let mut suggested_deref = false;
                             if let Ref(_, rty, _) = lhs_ty.kind {
@@ -678,13 +686,19 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                         op.as_str(),
                         actual
                     );
-                    err.span_label(
+                    err.set_message_id(
+                        MessageId::new("typeck-unary-op-unsupported")
+                            .arg("op", op.as_str())
+                            .arg("ty", actual),
+                    );
+                    err.span_label_with_id(
                         ex.span,
                         format!(
                             "cannot apply unary \
                                                     operator `{}`",
                             op.as_str()
                         ),
+                        MessageId::new("typeck-unary-op-unsupported-label").arg("op", op.as_str()),
                     );
                     match actual.kind {
                         Uint(_) if op == hir::UnOp::UnNeg => {
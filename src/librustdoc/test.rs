@@ -717,6 +717,7 @@ impl Tester for Collector {
                 should_panic: testing::ShouldPanic::No,
                 allow_fail: config.allow_fail,
                 test_type: testing::TestType::DocTest,
+                timeout: None,
             },
             testfn: testing::DynTestFn(box move || {
                 let res = run_test(
@@ -2,10 +2,11 @@
 
 use crate::io::prelude::*;
 
-use crate::cell::RefCell;
+use crate::cell::{Cell, RefCell};
 use crate::fmt;
 use crate::io::lazy::Lazy;
 use crate::io::{self, BufReader, Initializer, IoSlice, IoSliceMut, LineWriter};
+use crate::sync::atomic::{AtomicBool, Ordering};
 use crate::sync::{Arc, Mutex, MutexGuard};
 use crate::sys::stdio;
 use crate::sys_common::remutex::{ReentrantMutex, ReentrantMutexGuard};
@@ -25,6 +26,18 @@ thread_local! {
     }
 }
 
+thread_local! {
+    /// Buffer capturing the output of print!, eprint! and panics, which is
+    /// inherited by the threads spawned by this thread
+    static OUTPUT_CAPTURE: Cell<Option<Arc<Mutex<Vec<u8>>>>> = {
+        Cell::new(None)
+    }
+}
+
+/// Set once an output capture buffer has been installed on any thread, so that
+/// the thread local above isn't touched by programs that never capture output.
+static OUTPUT_CAPTURE_USED: AtomicBool = AtomicBool::new(false);
+
 /// A handle to a raw instance of the standard input stream of this process.
 ///
 /// This handle is not synchronized or buffered in any fashion. Constructed via
@@ -772,8 +785,56 @@ pub fn set_print(sink: Option<Box<dyn Write + Send>>) -> Option<Box<dyn Write +
     })
 }
 
-/// Write `args` to output stream `local_s` if possible, `global_s`
-/// otherwise. `label` identifies the stream in a panic message.
+/// Resets the thread-local output capture buffer to the specified buffer
+///
+/// This will replace the current thread's output capture buffer, returning
+/// the old buffer. All future calls to `print!`, `eprint!`, `panic!` and
+/// friends will append their output to this buffer, unless a handle was set
+/// with `set_print` or `set_panic`.
+///
+/// Unlike the handles set with `set_print` and `set_panic`, the buffer is
+/// inherited by every thread spawned by this thread afterwards.
+#[unstable(
+    feature = "set_stdio",
+    reason = "this function may disappear completely or be replaced \
+                     with a more general mechanism",
+    issue = "none"
+)]
+#[doc(hidden)]
+pub fn set_output_capture(sink: Option<Arc<Mutex<Vec<u8>>>>) -> Option<Arc<Mutex<Vec<u8>>>> {
+    if sink.is_none() && !OUTPUT_CAPTURE_USED.load(Ordering::Relaxed) {
+        // No thread has captured its output yet, so there's nothing to replace.
+        return None;
+    }
+    OUTPUT_CAPTURE_USED.store(true, Ordering::Relaxed);
+    OUTPUT_CAPTURE.try_with(move |slot| slot.replace(sink)).ok().and_then(|s| s)
+}
+
+/// Passes the output capture buffer of this thread to `f`, if there is one.
+///
+/// Returns `false` if the thread doesn't capture its output. A poisoned buffer
+/// is still written to, as a panicking writer can't leave it in an
+/// inconsistent state.
+pub(crate) fn with_output_capture(f: impl FnOnce(&mut dyn Write)) -> bool {
+    if !OUTPUT_CAPTURE_USED.load(Ordering::Relaxed) {
+        return false;
+    }
+    OUTPUT_CAPTURE
+        .try_with(|slot| {
+            let capture = slot.take();
+            if let Some(ref capture) = capture {
+                f(&mut *capture.lock().unwrap_or_else(|e| e.into_inner()));
+            }
+            let captured = capture.is_some();
+            slot.set(capture);
+            captured
+        })
+        .unwrap_or(false)
+}
+
+/// Write `args` to output stream `local_s` if possible, to the output capture
+/// buffer if there is one, and to `global_s` otherwise. `label` identifies the
+/// stream in a panic message.
 ///
 /// This function is used to print error messages, so it takes extra
 /// care to avoid causing a panic when `local_s` is unusable.
@@ -797,6 +858,10 @@ fn print_to<T>(
                     return w.write_fmt(args);
                 }
             }
+            let mut result = Ok(());
+            if with_output_capture(|w| result = w.write_fmt(args)) {
+                return result;
+            }
             global_s().write_fmt(args)
         })
         .unwrap_or_else(|_| global_s().write_fmt(args));
@@ -224,6 +224,8 @@
     all(test, target_vendor = "fortanix", target_env = "sgx"),
     feature(fixed_size_array, maybe_uninit_extra)
 )]
+#![cfg_attr(not(bootstrap), feature(must_not_suspend))]
+#![cfg_attr(not(bootstrap), feature(has_significant_drop))]
 // std is implemented with unstable features, many of which are internal
 // compiler details that will never be stable
 // NB: the following list is sorted to minimize merge conflicts.
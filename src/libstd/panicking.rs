@@ -220,6 +220,8 @@ fn default_hook(info: &PanicInfo<'_>) {
         // for `Box<dyn (::realstd::io::Write) + Send>`.
         write(&mut local);
         set_panic(Some(local));
+    } else if crate::io::with_output_capture(|out| write(out)) {
+        // The message was written to the output capture buffer.
     } else if let Some(mut out) = panic_output() {
         write(&mut out);
     }
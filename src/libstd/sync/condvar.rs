@@ -818,6 +818,6 @@ mod tests {
         drop(g);
 
         let m = Mutex::new(());
-        let _ = c.wait(m.lock().unwrap()).unwrap();
+        drop(c.wait(m.lock().unwrap()).unwrap());
     }
 }
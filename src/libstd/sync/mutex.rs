@@ -141,6 +141,12 @@ unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
 /// [`try_lock`]: struct.Mutex.html#method.try_lock
 /// [`Mutex`]: struct.Mutex.html
 #[must_use = "if unused the Mutex will immediately unlock"]
+#[cfg_attr(
+    not(bootstrap),
+    must_not_suspend = "holding a `MutexGuard` across suspension points can cause deadlocks, \
+                        and makes the future `!Send`"
+)]
+#[cfg_attr(not(bootstrap), has_significant_drop = "dropping a `MutexGuard` unlocks the `Mutex`")]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct MutexGuard<'a, T: ?Sized + 'a> {
     lock: &'a Mutex<T>,
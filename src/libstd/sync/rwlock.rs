@@ -85,6 +85,15 @@ unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
 /// [`try_read`]: struct.RwLock.html#method.try_read
 /// [`RwLock`]: struct.RwLock.html
 #[must_use = "if unused the RwLock will immediately unlock"]
+#[cfg_attr(
+    not(bootstrap),
+    must_not_suspend = "holding a `RwLockReadGuard` across suspension points can cause \
+                        deadlocks, and makes the future `!Send`"
+)]
+#[cfg_attr(
+    not(bootstrap),
+    has_significant_drop = "dropping a `RwLockReadGuard` releases the shared access to the `RwLock`"
+)]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct RwLockReadGuard<'a, T: ?Sized + 'a> {
     lock: &'a RwLock<T>,
@@ -106,6 +115,16 @@ unsafe impl<T: ?Sized + Sync> Sync for RwLockReadGuard<'_, T> {}
 /// [`try_write`]: struct.RwLock.html#method.try_write
 /// [`RwLock`]: struct.RwLock.html
 #[must_use = "if unused the RwLock will immediately unlock"]
+#[cfg_attr(
+    not(bootstrap),
+    must_not_suspend = "holding a `RwLockWriteGuard` across suspension points can cause \
+                        deadlocks, and makes the future `!Send`"
+)]
+#[cfg_attr(
+    not(bootstrap),
+    has_significant_drop = "dropping a `RwLockWriteGuard` releases the exclusive access to the \
+                            `RwLock`"
+)]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct RwLockWriteGuard<'a, T: ?Sized + 'a> {
     lock: &'a RwLock<T>,
@@ -465,11 +465,18 @@ impl Builder {
         let my_packet: Arc<UnsafeCell<Option<Result<T>>>> = Arc::new(UnsafeCell::new(None));
         let their_packet = my_packet.clone();
 
+        // The output of the new thread is captured like the output of this one.
+        // This doesn't access the thread local until some thread captured its output.
+        let output_capture = io::set_output_capture(None);
+        io::set_output_capture(output_capture.clone());
+
         let main = move || {
             if let Some(name) = their_thread.cname() {
                 imp::Thread::set_name(name);
             }
 
+            io::set_output_capture(output_capture);
+
             thread_info::set(imp::guard::current(), their_thread);
             let try_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
                 crate::sys_common::backtrace::__rust_begin_short_backtrace(f)
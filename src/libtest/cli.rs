@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::helpers::isatty;
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::options::{CaptureMode, ColorConfig, Options, OutputFormat, RunIgnored, Shard};
 use super::time::TestTimeOptions;
 
 #[derive(Debug)]
@@ -13,17 +14,26 @@ pub struct TestOpts {
     pub filter: Option<String>,
     pub filter_exact: bool,
     pub force_run_in_process: bool,
+    pub isolate: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
     pub run_tests: bool,
     pub bench_benchmarks: bool,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
     pub logfile: Option<PathBuf>,
     pub nocapture: bool,
+    pub capture: CaptureMode,
     pub color: ColorConfig,
     pub format: OutputFormat,
     pub test_threads: Option<usize>,
+    pub test_timeout: Option<Duration>,
+    pub retries: usize,
     pub skip: Vec<String>,
+    pub shard: Option<Shard>,
     pub time_options: Option<TestTimeOptions>,
+    pub shuffle: bool,
+    pub shuffle_seed: Option<u64>,
     pub options: Options,
 }
 
@@ -47,9 +57,30 @@ fn optgroups() -> getopts::Options {
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
         .optflag("", "force-run-in-process", "Forces tests to run in-process when panic=abort")
+        .optflag(
+            "",
+            "isolate",
+            "Run each test in its own subprocess, so that a crashing test \
+             doesn't bring down the whole run",
+        )
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the results of the benchmarks under the given name, \
+             so that later runs can be compared to them with --baseline",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the results of the benchmarks with the ones saved \
+             with --save-baseline under the given name, and report \
+             significant changes",
+            "NAME",
+        )
         .optflag("", "list", "List all tests and benchmarks")
         .optflag("h", "help", "Display this message (longer with --help)")
         .optopt(
@@ -65,6 +96,18 @@ fn optgroups() -> getopts::Options {
             "don't capture stdout/stderr of each \
              task, allow printing directly",
         )
+        .optopt(
+            "",
+            "capture",
+            "Configure how the output of tests running in-process is captured:
+            local   = capture the output of the test's thread (default);
+            inherit = also capture the output of the threads spawned by the test;
+            fd      = redirect the standard output and error of the process, which
+                      also captures the output of child processes and foreign code.
+                      Only used when tests run one at a time (--test-threads=1),
+                      otherwise this is the same as `inherit`",
+            "local|inherit|fd",
+        )
         .optopt(
             "",
             "test-threads",
@@ -72,6 +115,27 @@ fn optgroups() -> getopts::Options {
              in parallel",
             "n_threads",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Terminate tests that run for longer than the given number of \
+             seconds and report them as failed. Tests can only be terminated \
+             when they run in a subprocess (panic=abort or --isolate), or at \
+             an `.await` for async tests; tests running in-process cannot be \
+             interrupted, and are reported as failed once they complete. This \
+             can be overridden for a single test with the `#[test_timeout]` \
+             attribute",
+            "SECONDS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failing tests again up to N times. A test that passes on \
+             a retry is reported as flaky instead of failed. Only tests \
+             defined with a plain function, such as `#[test]` functions, \
+             can be retried",
+            "N",
+        )
         .optmulti(
             "",
             "skip",
@@ -79,6 +143,21 @@ fn optgroups() -> getopts::Options {
              be used multiple times)",
             "FILTER",
         )
+        .optopt(
+            "",
+            "shard-index",
+            "Only run the tests of the shard with the given zero-based index. \
+             Requires --shard-count",
+            "N",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Split the tests into this number of shards. Every test belongs to \
+             exactly one shard, which only depends on the name of the test. \
+             Requires --shard-index",
+            "M",
+        )
         .optflag(
             "q",
             "quiet",
@@ -101,8 +180,9 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output a json document;
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -143,6 +223,26 @@ fn optgroups() -> getopts::Options {
 
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
+        )
+        .optflag(
+            "",
+            "shuffle",
+            "Run tests in random order. The seed used for shuffling is printed
+            at the start of the run, so that the same order can be replayed with
+            `--shuffle-seed`.
+
+            This can also be enabled by setting the `RUST_TEST_SHUFFLE`
+            environment variable to a value other than \"0\".",
+        )
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order, shuffled with the given seed. Implies
+            `--shuffle`.
+
+            The seed can also be provided via the `RUST_TEST_SHUFFLE_SEED`
+            environment variable.",
+            "SEED",
         );
     opts
 }
@@ -222,6 +322,21 @@ macro_rules! unstable_optflag {
     }};
 }
 
+// Gets the option value and checks if unstable features are enabled.
+macro_rules! unstable_optopt {
+    ($matches:ident, $allow_unstable:ident, $option_name:literal) => {{
+        let opt = $matches.opt_str($option_name);
+        if !$allow_unstable && opt.is_some() {
+            return Err(format!(
+                "The \"{}\" option is only accepted on the nightly compiler",
+                $option_name
+            ));
+        }
+
+        opt
+    }};
+}
+
 // Implementation of `parse_opts` that doesn't care about help message
 // and returns a `Result`.
 fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
@@ -229,9 +344,21 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     // Unstable flags
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    if isolate && force_run_in_process {
+        return Err(
+            "the options --isolate and --force-run-in-process are mutually exclusive".into()
+        );
+    }
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let include_ignored = unstable_optflag!(matches, allow_unstable, "include-ignored");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let (save_baseline, baseline) = get_baselines(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
+    let shuffle = get_shuffle(&matches, allow_unstable)?;
+    let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
 
     let quiet = matches.opt_present("quiet");
     let exact = matches.opt_present("exact");
@@ -245,6 +372,7 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let run_ignored = get_run_ignored(&matches, include_ignored)?;
     let filter = get_filter(&matches)?;
     let nocapture = get_nocapture(&matches)?;
+    let capture = get_capture(&matches, allow_unstable)?;
     let test_threads = get_test_threads(&matches)?;
     let color = get_color_config(&matches)?;
     let format = get_format(&matches, quiet, allow_unstable)?;
@@ -256,17 +384,26 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         filter,
         filter_exact: exact,
         force_run_in_process,
+        isolate,
         exclude_should_panic,
         run_ignored,
         run_tests,
         bench_benchmarks,
+        save_baseline,
+        baseline,
         logfile,
         nocapture,
+        capture,
         color,
         format,
         test_threads,
+        test_timeout,
+        retries,
         skip,
+        shard,
         time_options,
+        shuffle,
+        shuffle_seed,
         options,
     };
 
@@ -326,6 +463,130 @@ fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     Ok(test_threads)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(0) => return Err("argument for --test-timeout must not be 0".to_string()),
+            Ok(n) => Some(Duration::from_secs(n)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number > 0 \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!("argument for --retries must be a number (error: {})", e));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
+fn get_baselines(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<String>, Option<String>)> {
+    let save_baseline = unstable_optopt!(matches, allow_unstable, "save-baseline");
+    let baseline = unstable_optopt!(matches, allow_unstable, "baseline");
+
+    // The names are used as directory names.
+    for name in save_baseline.iter().chain(baseline.iter()) {
+        if name.is_empty() || name.starts_with('.') || name.contains(|c| c == '/' || c == '\\') {
+            return Err(format!("invalid baseline name `{}`", name));
+        }
+    }
+
+    Ok((save_baseline, baseline))
+}
+
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Shard>> {
+    let parse = |name: &str, value: String| {
+        value
+            .parse::<usize>()
+            .map_err(|e| format!("argument for --{} must be a number (error: {})", name, e))
+    };
+    let index = unstable_optopt!(matches, allow_unstable, "shard-index");
+    let count = unstable_optopt!(matches, allow_unstable, "shard-count");
+
+    let shard = match (index, count) {
+        (None, None) => None,
+        (Some(index), Some(count)) => {
+            let index = parse("shard-index", index)?;
+            let count = parse("shard-count", count)?;
+            if count == 0 {
+                return Err("argument for --shard-count must not be 0".into());
+            }
+            if index >= count {
+                return Err(format!(
+                    "argument for --shard-index must be less than --shard-count \
+                     (was {}, with {} shards)",
+                    index, count
+                ));
+            }
+            Some(Shard { index, count })
+        }
+        _ => return Err("the options --shard-index and --shard-count must be used together".into()),
+    };
+
+    Ok(shard)
+}
+
+fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
+    let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    Ok(shuffle)
+}
+
+fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<u64>> {
+    let mut shuffle_seed = match unstable_optopt!(matches, allow_unstable, "shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+    if shuffle_seed.is_none() && allow_unstable {
+        shuffle_seed = match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => panic!("RUST_TEST_SHUFFLE_SEED is `{}`, should be a number.", val),
+            },
+            Err(_) => None,
+        };
+    }
+
+    Ok(shuffle_seed)
+}
+
 fn get_format(
     matches: &getopts::Matches,
     quiet: bool,
@@ -341,10 +602,16 @@ fn get_format(
             }
             OutputFormat::Json
         }
+        Some("junit") => {
+            if !allow_unstable {
+                return Err("The \"junit\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Junit
+        }
 
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json or junit (was \
                  {})",
                 v
             ));
@@ -384,6 +651,25 @@ fn get_nocapture(matches: &getopts::Matches) -> OptPartRes<bool> {
     Ok(nocapture)
 }
 
+fn get_capture(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<CaptureMode> {
+    let capture = unstable_optopt!(matches, allow_unstable, "capture");
+    let capture = match capture.as_ref().map(|s| &**s) {
+        Some("local") | None => CaptureMode::Local,
+        Some("inherit") => CaptureMode::Inherit,
+        Some("fd") => CaptureMode::Fd,
+
+        Some(v) => {
+            return Err(format!("argument for --capture must be local, inherit or fd (was {})", v));
+        }
+    };
+
+    if capture != CaptureMode::Local && matches.opt_present("nocapture") {
+        return Err("the options --capture and --nocapture are mutually exclusive".into());
+    }
+
+    Ok(capture)
+}
+
 fn get_run_ignored(matches: &getopts::Matches, include_ignored: bool) -> OptPartRes<RunIgnored> {
     let run_ignored = match (include_ignored, matches.opt_present("ignored")) {
         (true, true) => {
@@ -3,16 +3,22 @@
 use std::fs::File;
 use std::io;
 use std::io::prelude::Write;
+use std::sync::Arc;
 
 use super::{
-    bench::fmt_bench_samples,
+    bench::{fmt_bench_samples, BenchSamples},
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    helpers::{
+        baseline::{Baseline, BenchComparison},
+        concurrency::get_concurrency,
+        metrics::MetricMap,
+    },
     options::{Options, OutputFormat},
     run_tests,
+    runner::{self, make_tests, Runner},
     test_result::TestResult,
     time::TestExecTime,
     types::{NamePadding, TestDesc, TestDescAndFn},
@@ -47,12 +53,15 @@ pub struct ConsoleTestState {
     pub failed: usize,
     pub ignored: usize,
     pub allowed_fail: usize,
+    pub flaky: usize,
     pub filtered_out: usize,
     pub measured: usize,
     pub metrics: MetricMap,
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub benchmarks: Vec<(TestDesc, BenchSamples)>,
+    pub baseline: Option<Baseline>,
     pub options: Options,
 }
 
@@ -70,12 +79,15 @@ impl ConsoleTestState {
             failed: 0,
             ignored: 0,
             allowed_fail: 0,
+            flaky: 0,
             filtered_out: 0,
             measured: 0,
             metrics: MetricMap::new(),
             failures: Vec::new(),
             not_failures: Vec::new(),
             time_failures: Vec::new(),
+            benchmarks: Vec::new(),
+            baseline: None,
             options: opts.options,
         })
     }
@@ -112,6 +124,7 @@ impl ConsoleTestState {
                     TestResult::TrAllowedFail => "failed (allowed)".to_owned(),
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrFlaky(retries) => format!("ok (flaky, {})", fmt_retries(retries)),
                 },
                 test.name,
             )
@@ -122,11 +135,25 @@ impl ConsoleTestState {
         self.write_log(|| "\n")
     }
 
+    /// Compares the result of a benchmark with the baseline, if one was given.
+    pub fn compare_to_baseline(
+        &self,
+        test: &TestDesc,
+        bs: &BenchSamples,
+    ) -> Option<BenchComparison> {
+        self.baseline.as_ref().and_then(|baseline| baseline.compare(test.name.as_slice(), bs))
+    }
+
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
+        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail + self.flaky
     }
 }
 
+/// Formats the number of retries of a flaky test, e.g. `2 retries`.
+pub(crate) fn fmt_retries(retries: usize) -> String {
+    format!("{} {}", retries, if retries == 1 { "retry" } else { "retries" })
+}
+
 // List the tests to console, and optionally to logfile. Filters are honored.
 pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<()> {
     let mut output = match term::stdout() {
@@ -189,12 +216,17 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
         }
         TestResult::TrIgnored => st.ignored += 1,
         TestResult::TrAllowedFail => st.allowed_fail += 1,
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.not_failures.push((test, stdout));
+        }
         TestResult::TrBench(bs) => {
             st.metrics.insert_metric(
                 test.name.as_slice(),
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.benchmarks.push((test, bs));
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -220,11 +252,12 @@ fn on_test_event(
     event: &TestEvent,
     st: &mut ConsoleTestState,
     out: &mut dyn OutputFormatter,
+    report: &mut dyn FnMut(&TestDesc, &TestResult),
 ) -> io::Result<()> {
     match (*event).clone() {
-        TestEvent::TeFiltered(ref filtered_tests) => {
+        TestEvent::TeFiltered(ref filtered_tests, shuffle_seed) => {
             st.total = filtered_tests.len();
-            out.write_run_start(filtered_tests.len())?;
+            out.write_run_start(filtered_tests.len(), shuffle_seed)?;
         }
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
@@ -237,6 +270,7 @@ fn on_test_event(
             let exec_time = &completed_test.exec_time;
             let stdout = &completed_test.stdout;
 
+            report(test, result);
             st.write_log_result(test, result, exec_time.as_ref())?;
             out.write_result(test, result, exec_time.as_ref(), &*stdout, st)?;
             handle_test_result(st, completed_test);
@@ -249,6 +283,27 @@ fn on_test_event(
 /// A simple console test runner.
 /// Runs provided tests reporting process and results to the stdout.
 pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<bool> {
+    run_tests_console_with_report(opts, tests, &mut |_, _| {})
+}
+
+/// Like `run_tests_console`, but runs the tests of a custom runner, which is
+/// also given the result of every test.
+pub fn run_tests_console_with_runner<R: Runner>(
+    opts: &TestOpts,
+    runner: R,
+    tests: Vec<R::Test>,
+) -> io::Result<bool> {
+    runner::check_opts(opts).map_err(|msg| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
+    let runner = Arc::new(runner);
+    let tests = make_tests(&runner, tests);
+    run_tests_console_with_report(opts, tests, &mut |desc, result| runner.report(desc, result))
+}
+
+fn run_tests_console_with_report(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+    report: &mut dyn FnMut(&TestDesc, &TestResult),
+) -> io::Result<bool> {
     let output = match term::stdout() {
         None => OutputLocation::Raw(io::stdout()),
         Some(t) => OutputLocation::Pretty(t),
@@ -274,13 +329,26 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
             Box::new(TerseFormatter::new(output, opts.use_color(), max_name_len, is_multithreaded))
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
+    if let Some(ref name) = opts.baseline {
+        st.baseline = Some(Baseline::load(name)?);
+    }
 
-    run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
+    run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out, report))?;
 
     assert!(st.current_test_count() == st.total);
 
+    if let Some(ref name) = opts.save_baseline {
+        // Saving no results would only drop the results of a previous run.
+        if st.benchmarks.is_empty() {
+            eprintln!("warning: no benchmarks were run, so the baseline `{}` was not saved", name);
+        } else {
+            Baseline::save(name, &st.benchmarks)?;
+        }
+    }
+
     out.write_run_finish(&st)
 }
 
@@ -0,0 +1,115 @@
+//! A minimal single-threaded executor used to run `async` tests.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::future::Future;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// The time limit of the test running on this thread, with the instant it
+    /// started at.
+    static TEST_TIMEOUT: Cell<Option<(Instant, Duration)>> = Cell::new(None);
+}
+
+/// Sets the time limit of the test about to run on this thread, which is
+/// enforced by `block_on`.
+pub(crate) fn set_test_timeout(timeout: Option<Duration>) {
+    TEST_TIMEOUT.with(|t| t.set(timeout.map(|timeout| (Instant::now(), timeout))));
+}
+
+/// The payload of the panic raised by `block_on` when the test runs for longer
+/// than it is allowed to.
+struct TimedOut;
+
+/// Returns whether a test panicked because it ran for too long.
+pub(crate) fn is_timed_out(payload: &(dyn Any + Send)) -> bool {
+    payload.is::<TimedOut>()
+}
+
+/// Wakes up the thread running `block_on`.
+struct Signal {
+    thread: Thread,
+    woken: AtomicBool,
+}
+
+impl Signal {
+    fn wake(&self) {
+        self.woken.store(true, Ordering::SeqCst);
+        self.thread.unpark();
+    }
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+fn raw_waker(signal: Arc<Signal>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(signal) as *const (), &VTABLE)
+}
+
+unsafe fn clone(ptr: *const ()) -> RawWaker {
+    let signal = Arc::from_raw(ptr as *const Signal);
+    let cloned = signal.clone();
+    // The waker being cloned still owns its reference.
+    let _ = Arc::into_raw(signal);
+    raw_waker(cloned)
+}
+
+unsafe fn wake(ptr: *const ()) {
+    Arc::from_raw(ptr as *const Signal).wake();
+}
+
+unsafe fn wake_by_ref(ptr: *const ()) {
+    (*(ptr as *const Signal)).wake();
+}
+
+unsafe fn drop(ptr: *const ()) {
+    let _ = Arc::from_raw(ptr as *const Signal);
+}
+
+/// Runs a future to completion on the current thread. This is how the
+/// functions of `async` tests are run.
+///
+/// The test fails if it runs for longer than its time limit, or if its future
+/// is pending while no `Waker` that could wake it up is left.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let signal = Arc::new(Signal { thread: thread::current(), woken: AtomicBool::new(false) });
+    let waker = unsafe { Waker::from_raw(raw_waker(signal.clone())) };
+    let mut cx = Context::from_waker(&waker);
+    let deadline = TEST_TIMEOUT.with(|t| t.get()).map(|(start, timeout)| start + timeout);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        loop {
+            if signal.woken.swap(false, Ordering::SeqCst) {
+                break;
+            }
+            // Only `signal` and `waker` are left, so nothing can wake the
+            // future up anymore.
+            if Arc::strong_count(&signal) == 2 && !signal.woken.load(Ordering::SeqCst) {
+                panic!(
+                    "the future of the test is pending, but it can never be woken up \
+                     as every `Waker` it was given has been dropped"
+                );
+            }
+
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        panic::resume_unwind(Box::new(TimedOut));
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+                None => thread::park(),
+            }
+        }
+    }
+}
@@ -57,10 +57,15 @@ impl<T: Write> JsonFormatter<T> {
 }
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        let shuffle_seed_json = if let Some(shuffle_seed) = shuffle_seed {
+            format!(r#", "shuffle_seed": {}"#, shuffle_seed)
+        } else {
+            String::new()
+        };
         self.writeln_message(&*format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
-            test_count
+            r#"{{ "type": "suite", "event": "started", "test_count": {}{} }}"#,
+            test_count, shuffle_seed_json
         ))
     }
 
@@ -125,6 +130,15 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                 None,
             ),
 
+            TestResult::TrFlaky(retries) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "flaky",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""retries": {}"#, retries)),
+            ),
+
             TestResult::TrBench(ref bs) => {
                 let median = bs.ns_iter_summ.median as usize;
                 let deviation = (bs.ns_iter_summ.max - bs.ns_iter_summ.min) as usize;
@@ -135,12 +149,22 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
 
+                let baseline = match state.compare_to_baseline(desc, bs) {
+                    Some(comparison) => format!(
+                        r#", "baseline": {{ "change": {}, "p_value": {}, "regression": {} }}"#,
+                        comparison.change_pct,
+                        comparison.p_value,
+                        comparison.is_regression()
+                    ),
+                    None => String::new(),
+                };
+
                 let line = format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{}\", \
                      \"median\": {}, \
-                     \"deviation\": {}{} }}",
-                    desc.name, median, deviation, mbps
+                     \"deviation\": {}{}{} }}",
+                    desc.name, median, deviation, mbps, baseline
                 );
 
                 self.writeln_message(&*line)
@@ -162,6 +186,7 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
              \"passed\": {}, \
              \"failed\": {}, \
              \"allowed_fail\": {}, \
+             \"flaky\": {}, \
              \"ignored\": {}, \
              \"measured\": {}, \
              \"filtered_out\": {} }}",
@@ -169,6 +194,7 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
             state.passed,
             state.failed + state.allowed_fail,
             state.allowed_fail,
+            state.flaky,
             state.ignored,
             state.measured,
             state.filtered_out
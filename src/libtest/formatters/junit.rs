@@ -0,0 +1,224 @@
+use std::{
+    fmt,
+    io::{self, prelude::Write},
+    time::Duration,
+};
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    results: Vec<(TestDesc, TestResult, Duration, Vec<u8>)>,
+    shuffle_seed: Option<u64>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, results: Vec::new(), shuffle_seed: None }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_message(&mut self, s: &str) -> io::Result<()> {
+        assert!(!s.contains('\n'));
+
+        self.out.write_all(s.as_ref())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_testcase_start(&mut self, desc: &TestDesc, duration: Duration) -> io::Result<()> {
+        let (class_name, test_name) = split_test_name(desc);
+        self.write_message(&*format!(
+            r#"<testcase classname="{}" name="{}" time="{:.3}">"#,
+            EscapedString(class_name),
+            EscapedString(test_name),
+            duration.as_secs_f64()
+        ))
+    }
+
+    fn write_failure(&mut self, kind: &str, message: Option<&str>) -> io::Result<()> {
+        match message {
+            Some(message) => self.write_message(&*format!(
+                r#"<failure type="{}" message="{}"/>"#,
+                kind,
+                EscapedString(message)
+            )),
+            None => self.write_message(&*format!(r#"<failure type="{}"/>"#, kind)),
+        }
+    }
+
+    fn write_system_out(&mut self, stdout: &[u8]) -> io::Result<()> {
+        if stdout.is_empty() {
+            return Ok(());
+        }
+        let stdout = String::from_utf8_lossy(stdout);
+        self.write_message(&*format!("<system-out>{}</system-out>", EscapedString(stdout)))
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.shuffle_seed = shuffle_seed;
+        self.write_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Nothing is written until the whole run is complete.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // JUnit has no notion of a test that is still running.
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        _state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        // The `testsuite` element carries the test counts as attributes, so it
+        // can't be written until every test has completed. Collect the results
+        // here and write them all at once in `write_run_finish`.
+        let duration = exec_time.map(|t| t.0).unwrap_or_default();
+        self.results.push((desc.clone(), result.clone(), duration, stdout.to_vec()));
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let total_time: Duration = self.results.iter().map(|(_, _, duration, _)| *duration).sum();
+
+        self.write_message("<testsuites>")?;
+        self.write_message(&*format!(
+            "<testsuite name=\"test\" package=\"test\" id=\"0\" \
+             errors=\"0\" \
+             failures=\"{}\" \
+             tests=\"{}\" \
+             skipped=\"{}\" \
+             time=\"{:.3}\">",
+            state.failed,
+            state.total - state.measured,
+            state.ignored + state.allowed_fail,
+            total_time.as_secs_f64()
+        ))?;
+
+        if let Some(shuffle_seed) = self.shuffle_seed {
+            self.write_message("<properties>")?;
+            self.write_message(&*format!(
+                r#"<property name="shuffle_seed" value="{}"/>"#,
+                shuffle_seed
+            ))?;
+            self.write_message("</properties>")?;
+        }
+
+        for (desc, result, duration, stdout) in std::mem::replace(&mut self.results, Vec::new()) {
+            if let TestResult::TrBench(_) = result {
+                // Benchmarks are not tests, so they have no place in the report.
+                continue;
+            }
+
+            self.write_testcase_start(&desc, duration)?;
+            match result {
+                TestResult::TrOk => {
+                    if state.options.display_output {
+                        self.write_system_out(&stdout)?;
+                    }
+                }
+                TestResult::TrFailed => {
+                    self.write_failure("assert", None)?;
+                    self.write_system_out(&stdout)?;
+                }
+                TestResult::TrFailedMsg(ref m) => {
+                    self.write_failure("assert", Some(m))?;
+                    self.write_system_out(&stdout)?;
+                }
+                TestResult::TrTimedFail => {
+                    self.write_failure("timeout", Some("time limit exceeded"))?;
+                    self.write_system_out(&stdout)?;
+                }
+                TestResult::TrAllowedFail => {
+                    // The run passes regardless, so consumers mustn't count
+                    // the test as a failure.
+                    self.write_message(r#"<skipped message="failure allowed"/>"#)?;
+                    self.write_system_out(&stdout)?;
+                }
+                TestResult::TrFlaky(_) => {
+                    // The output contains the failures of the previous attempts.
+                    self.write_system_out(&stdout)?;
+                }
+                TestResult::TrIgnored => self.write_message("<skipped/>")?,
+                TestResult::TrBench(_) => unreachable!(),
+            }
+            self.write_message("</testcase>")?;
+        }
+
+        self.write_message("</testsuite>")?;
+        self.write_message("</testsuites>")?;
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// Splits a test path such as `module::submodule::test` into the class name
+/// (`module::submodule`) and the name of the test itself (`test`), which is how
+/// JUnit consumers group test cases.
+fn split_test_name(desc: &TestDesc) -> (&str, &str) {
+    let name = desc.name.as_slice();
+    match name.rfind("::") {
+        Some(idx) => (&name[..idx], &name[idx + 2..]),
+        None => ("", name),
+    }
+}
+
+/// A formatting utility used to print strings with characters in need of escaping
+/// inside of XML text and attribute values.
+struct EscapedString<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> fmt::Display for EscapedString<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut start = 0;
+
+        for (i, byte) in self.0.as_ref().bytes().enumerate() {
+            let escaped = match byte {
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'&' => "&amp;",
+                b'"' => "&quot;",
+                b'\'' => "&apos;",
+                b'\n' => "&#10;",
+                b'\r' => "&#13;",
+                // Control characters other than `\t` are not allowed in XML 1.0 at all.
+                b'\x00'..=b'\x08' | b'\x0b' | b'\x0c' | b'\x0e'..=b'\x1f' => "\u{fffd}",
+                _ => {
+                    continue;
+                }
+            };
+
+            if start < i {
+                f.write_str(&self.0.as_ref()[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + 1;
+        }
+
+        if start != self.0.as_ref().len() {
+            f.write_str(&self.0.as_ref()[start..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -3,7 +3,8 @@ use std::{io, io::prelude::Write};
 use super::OutputFormatter;
 use crate::{
     bench::fmt_bench_samples,
-    console::{ConsoleTestState, OutputLocation},
+    console::{fmt_retries, ConsoleTestState, OutputLocation},
+    helpers::baseline::BenchComparison,
     test_result::TestResult,
     time,
     types::TestDesc,
@@ -56,10 +57,25 @@ impl<T: Write> PrettyFormatter<T> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub fn write_flaky(&mut self, retries: usize) -> io::Result<()> {
+        let result = format!("ok (flaky, {})", fmt_retries(retries));
+        self.write_short_result(&result, term::color::YELLOW)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
 
+    pub fn write_bench_comparison(&mut self, comparison: &BenchComparison) -> io::Result<()> {
+        self.write_plain(&format!(" change: {}", comparison))?;
+        if comparison.is_regression() {
+            self.write_pretty(" regressed", term::color::RED)?;
+        } else if comparison.is_improvement() {
+            self.write_pretty(" improved", term::color::GREEN)?;
+        }
+        Ok(())
+    }
+
     pub fn write_short_result(
         &mut self,
         result: &str,
@@ -176,9 +192,14 @@ impl<T: Write> PrettyFormatter<T> {
 }
 
 impl<T: Write> OutputFormatter for PrettyFormatter<T> {
-    fn write_run_start(&mut self, test_count: usize) -> io::Result<()> {
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
         let noun = if test_count != 1 { "tests" } else { "test" };
-        self.write_plain(&format!("\nrunning {} {}\n", test_count, noun))
+        let shuffle_seed_msg = if let Some(shuffle_seed) = shuffle_seed {
+            format!(" (shuffle seed: {})", shuffle_seed)
+        } else {
+            String::new()
+        };
+        self.write_plain(&format!("\nrunning {} {}{}\n", test_count, noun, shuffle_seed_msg))
     }
 
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()> {
@@ -199,7 +220,7 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         result: &TestResult,
         exec_time: Option<&time::TestExecTime>,
         _: &[u8],
-        _: &ConsoleTestState,
+        state: &ConsoleTestState,
     ) -> io::Result<()> {
         if self.is_multithreaded {
             self.write_test_name(desc)?;
@@ -213,8 +234,12 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             TestResult::TrBench(ref bs) => {
                 self.write_bench()?;
                 self.write_plain(&format!(": {}", fmt_bench_samples(bs)))?;
+                if let Some(comparison) = state.compare_to_baseline(desc, bs) {
+                    self.write_bench_comparison(&comparison)?;
+                }
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrFlaky(retries) => self.write_flaky(retries)?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -257,10 +282,15 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
+        let passed = if state.flaky > 0 {
+            format!("{} passed; {} flaky", state.passed, state.flaky)
+        } else {
+            format!("{} passed", state.passed)
+        };
         let s = if state.allowed_fail > 0 {
             format!(
-                ". {} passed; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
-                state.passed,
+                ". {}; {} failed ({} allowed); {} ignored; {} measured; {} filtered out\n\n",
+                passed,
                 state.failed + state.allowed_fail,
                 state.allowed_fail,
                 state.ignored,
@@ -269,8 +299,8 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
             )
         } else {
             format!(
-                ". {} passed; {} failed; {} ignored; {} measured; {} filtered out\n\n",
-                state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+                ". {}; {} failed; {} ignored; {} measured; {} filtered out\n\n",
+                passed, state.failed, state.ignored, state.measured, state.filtered_out
             )
         };
 
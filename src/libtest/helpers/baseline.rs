@@ -0,0 +1,176 @@
+//! Helper module to save the results of benchmarks and compare later runs
+//! against them.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::bench::BenchSamples;
+use crate::stats::{self, Stats};
+use crate::types::TestDesc;
+
+/// A change is only reported when the probability of it being caused by noise
+/// is lower than this.
+const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Changes of the median smaller than this percentage are never reported, as
+/// they are within the usual noise of a benchmark.
+const NOISE_THRESHOLD_PCT: f64 = 2.0;
+
+/// The samples of the benchmarks of a previous run, by benchmark name.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    benches: HashMap<String, Vec<f64>>,
+}
+
+impl Baseline {
+    #[cfg(test)]
+    pub fn new(benches: HashMap<String, Vec<f64>>) -> Baseline {
+        Baseline { benches }
+    }
+
+    /// Returns the file the baseline with the given name is saved to.
+    ///
+    /// Baselines are kept next to the test executable, in a file named after
+    /// it, so that every test executable has its own set of baselines.
+    fn path(name: &str) -> io::Result<PathBuf> {
+        let exe = env::current_exe()?;
+        let file_name = exe.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Other, "test executable has no file name")
+        })?;
+        let mut path = exe.with_file_name("baselines");
+        path.push(name);
+        path.push(file_name);
+        path.set_extension("txt");
+        Ok(path)
+    }
+
+    /// Reads the baseline with the given name.
+    pub fn load(name: &str) -> io::Result<Baseline> {
+        let path = Baseline::path(name)?;
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to read baseline `{}` from {}: {}", name, path.display(), e),
+            )
+        })?;
+
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("baseline file {} is malformed", path.display()),
+            )
+        };
+        let mut benches = HashMap::new();
+        // Every line contains the samples of a benchmark followed by its name.
+        for line in contents.lines() {
+            let mut parts = line.splitn(2, '\t');
+            let samples = parts.next().ok_or_else(invalid)?;
+            let bench_name = parts.next().ok_or_else(invalid)?;
+            let samples = samples
+                .split(' ')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+            benches.insert(bench_name.to_string(), samples);
+        }
+
+        Ok(Baseline { benches })
+    }
+
+    /// Saves the results of the benchmarks to the baseline with the given name.
+    /// The benchmarks which were not run keep their results from the previous
+    /// baseline of the same name, if any, so that a run of some benchmarks only
+    /// updates theirs.
+    pub fn save(name: &str, results: &[(TestDesc, BenchSamples)]) -> io::Result<()> {
+        let path = Baseline::path(name)?;
+        let mut baseline = match Baseline::load(name) {
+            Ok(baseline) => baseline,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Baseline::default(),
+            Err(e) => return Err(e),
+        };
+        baseline.merge(results);
+
+        // Sorted by name, so that saving the same results twice gives the same file.
+        let mut benches: Vec<_> = baseline.benches.iter().collect();
+        benches.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+        let mut contents = String::new();
+        for (bench_name, samples) in benches {
+            let samples: Vec<_> = samples.iter().map(|s| s.to_string()).collect();
+            contents.push_str(&format!("{}\t{}\n", samples.join(" "), bench_name));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, contents).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to write baseline `{}` to {}: {}", name, path.display(), e),
+            )
+        })
+    }
+
+    /// Replaces the results of the given benchmarks, keeping the others.
+    pub fn merge(&mut self, results: &[(TestDesc, BenchSamples)]) {
+        for (desc, bs) in results {
+            let bench_name = desc.name.as_slice();
+            if bench_name.contains('\n') || bs.samples.is_empty() {
+                continue;
+            }
+            self.benches.insert(bench_name.to_string(), bs.samples.clone());
+        }
+    }
+
+    /// Compares the result of a benchmark with its result in the baseline, if
+    /// it was part of it.
+    pub fn compare(&self, name: &str, bs: &BenchSamples) -> Option<BenchComparison> {
+        let baseline = self.benches.get(name)?;
+        if baseline.is_empty() || bs.samples.is_empty() {
+            return None;
+        }
+
+        let baseline_median = baseline.as_slice().median();
+        if baseline_median == 0.0 {
+            return None;
+        }
+
+        Some(BenchComparison {
+            change_pct: (bs.ns_iter_summ.median - baseline_median) / baseline_median * 100.0,
+            p_value: stats::mann_whitney_u_test(baseline, &bs.samples),
+        })
+    }
+}
+
+/// How the result of a benchmark changed relative to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchComparison {
+    /// Change of the median time per iteration, in percent.
+    pub change_pct: f64,
+    /// Probability of the samples being this different by chance.
+    pub p_value: f64,
+}
+
+impl BenchComparison {
+    fn is_significant(&self) -> bool {
+        self.p_value < SIGNIFICANCE_LEVEL && self.change_pct.abs() >= NOISE_THRESHOLD_PCT
+    }
+
+    pub fn is_regression(&self) -> bool {
+        self.is_significant() && self.change_pct > 0.0
+    }
+
+    pub fn is_improvement(&self) -> bool {
+        self.is_significant() && self.change_pct < 0.0
+    }
+}
+
+impl fmt::Display for BenchComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.2}% (p = {:.3})", self.change_pct, self.p_value)
+    }
+}
@@ -0,0 +1,169 @@
+//! Helper module to capture the output of tests running in-process.
+
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use super::sink::Sink;
+use crate::options::CaptureMode;
+
+/// Captures the output of the test running on the current thread until it is
+/// finished.
+pub struct OutputCapture {
+    data: Arc<Mutex<Vec<u8>>>,
+    previous: Previous,
+}
+
+/// What has to be restored once the test is finished.
+enum Previous {
+    Sinks(Option<Box<dyn io::Write + Send>>, Option<Box<dyn io::Write + Send>>),
+    Capture(Option<Arc<Mutex<Vec<u8>>>>),
+    Redirect(fd::Redirect),
+}
+
+impl OutputCapture {
+    /// Starts capturing the output of the test about to run on this thread.
+    pub fn start(mode: CaptureMode) -> OutputCapture {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let redirect = match mode {
+            CaptureMode::Fd => fd::Redirect::start().ok(),
+            CaptureMode::Local | CaptureMode::Inherit => None,
+        };
+
+        let previous = match (mode, redirect) {
+            (_, Some(redirect)) => Previous::Redirect(redirect),
+            (CaptureMode::Local, None) => Previous::Sinks(
+                io::set_print(Some(Sink::new_boxed(&data))),
+                io::set_panic(Some(Sink::new_boxed(&data))),
+            ),
+            // If the file descriptors can't be redirected, at least the output
+            // written by Rust code is captured.
+            (CaptureMode::Inherit, None) | (CaptureMode::Fd, None) => {
+                Previous::Capture(io::set_output_capture(Some(data.clone())))
+            }
+        };
+
+        OutputCapture { data, previous }
+    }
+
+    /// Stops capturing and returns the captured output.
+    pub fn finish(self) -> Vec<u8> {
+        match self.previous {
+            Previous::Sinks(print, panic) => {
+                io::set_print(print);
+                io::set_panic(panic);
+            }
+            Previous::Capture(capture) => {
+                io::set_output_capture(capture);
+            }
+            Previous::Redirect(redirect) => {
+                return redirect.finish().unwrap_or_else(|e| {
+                    format!("failed to read the captured output: {}\n", e).into_bytes()
+                });
+            }
+        }
+        // A test that panicked while printing poisons the buffer, but what it
+        // wrote so far is still worth showing. The guard is dropped at the end
+        // of the statement, before `self.data` is.
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner()).to_vec();
+        data
+    }
+}
+
+#[cfg(unix)]
+mod fd {
+    use std::{
+        env,
+        fs::{self, File, OpenOptions},
+        io::{self, prelude::*, SeekFrom},
+        os::unix::io::AsRawFd,
+        process,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    fn cvt(ret: libc::c_int) -> io::Result<libc::c_int> {
+        if ret == -1 { Err(io::Error::last_os_error()) } else { Ok(ret) }
+    }
+
+    /// Creates an anonymous file to write the output to. A pipe can't be used,
+    /// as the test would block once it fills up.
+    fn temp_file() -> io::Result<File> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "rust-test-output-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = env::temp_dir().join(name);
+        let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+        fs::remove_file(&path)?;
+        Ok(file)
+    }
+
+    /// Redirects the standard output and error file descriptors of the process
+    /// to a file until it is dropped.
+    pub struct Redirect {
+        file: File,
+        /// The redirected descriptors, with duplicates of their original
+        /// descriptions.
+        saved: Vec<(libc::c_int, libc::c_int)>,
+    }
+
+    impl Redirect {
+        pub fn start() -> io::Result<Redirect> {
+            let file = temp_file()?;
+            // Output written before the test started must not be captured.
+            io::stdout().flush()?;
+
+            let mut redirect = Redirect { file, saved: Vec::new() };
+            for &fd in &[libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+                let saved = cvt(unsafe { libc::dup(fd) })?;
+                redirect.saved.push((fd, saved));
+                cvt(unsafe { libc::dup2(redirect.file.as_raw_fd(), fd) })?;
+            }
+            Ok(redirect)
+        }
+
+        /// Restores the file descriptors and returns the captured output.
+        pub fn finish(self) -> io::Result<Vec<u8>> {
+            let mut file = self.file.try_clone()?;
+            drop(self);
+
+            let mut output = Vec::new();
+            file.seek(SeekFrom::Start(0))?;
+            file.read_to_end(&mut output)?;
+            Ok(output)
+        }
+    }
+
+    impl Drop for Redirect {
+        fn drop(&mut self) {
+            // The output buffered by the test belongs to it.
+            let _ = io::stdout().flush();
+            for &(fd, saved) in &self.saved {
+                unsafe {
+                    libc::dup2(saved, fd);
+                    libc::close(saved);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod fd {
+    use std::io;
+
+    pub struct Redirect;
+
+    impl Redirect {
+        pub fn start() -> io::Result<Redirect> {
+            Err(io::Error::new(io::ErrorKind::Other, "file descriptors can't be redirected"))
+        }
+
+        pub fn finish(self) -> io::Result<Vec<u8>> {
+            Ok(Vec::new())
+        }
+    }
+}
@@ -13,8 +13,30 @@ pub fn get_exit_code(status: ExitStatus) -> Result<i32, String> {
     match status.code() {
         Some(code) => Ok(code),
         None => match status.signal() {
-            Some(signal) => Err(format!("child process exited with signal {}", signal)),
+            Some(signal) => match signal_name(signal) {
+                Some(name) => {
+                    Err(format!("child process exited with signal {} ({})", signal, name))
+                }
+                None => Err(format!("child process exited with signal {}", signal)),
+            },
             None => Err("child process exited with unknown signal".into()),
         },
     }
 }
+
+/// Returns the name of the signals that commonly terminate a crashing test.
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    match signal {
+        libc::SIGILL => Some("SIGILL"),
+        libc::SIGTRAP => Some("SIGTRAP"),
+        libc::SIGABRT => Some("SIGABRT"),
+        libc::SIGBUS => Some("SIGBUS"),
+        libc::SIGFPE => Some("SIGFPE"),
+        libc::SIGKILL => Some("SIGKILL"),
+        libc::SIGSEGV => Some("SIGSEGV"),
+        libc::SIGPIPE => Some("SIGPIPE"),
+        libc::SIGTERM => Some("SIGTERM"),
+        _ => None,
+    }
+}
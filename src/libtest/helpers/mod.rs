@@ -1,8 +1,12 @@
 //! Module with common helpers not directly related to tests
 //! but used in `libtest`.
 
+pub mod baseline;
+pub mod capture;
 pub mod concurrency;
 pub mod exit_code;
 pub mod isatty;
 pub mod metrics;
+pub mod shard;
+pub mod shuffle;
 pub mod sink;
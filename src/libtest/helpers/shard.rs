@@ -0,0 +1,21 @@
+//! Helper module to split the tests into shards that can be run on
+//! different machines.
+
+use crate::types::TestName;
+
+/// Returns the index of the shard that the test with the given name belongs to.
+///
+/// The hash is computed by hand rather than with `DefaultHasher`, as the
+/// assignment must not change between runs, even when the test binary is
+/// rebuilt with another version of the standard library.
+pub fn get_shard_index(name: &TestName, shard_count: usize) -> usize {
+    (fnv1a(name.as_slice().as_bytes()) % shard_count as u64) as usize
+}
+
+/// 64-bit FNV-1a hash.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
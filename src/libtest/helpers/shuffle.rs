@@ -0,0 +1,85 @@
+//! Helper module to shuffle the order in which tests are run, so that
+//! hidden dependencies between tests can be detected.
+
+use crate::cli::TestOpts;
+use crate::helpers::shard::fnv1a;
+use crate::types::{TestDescAndFn, TestName};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the seed to shuffle the tests with, or `None` if the tests
+/// should be run in their usual order. If shuffling was requested without
+/// an explicit seed, a new one is derived from the current time.
+pub fn get_shuffle_seed(opts: &TestOpts) -> Option<u64> {
+    opts.shuffle_seed.or_else(|| {
+        if opts.shuffle {
+            Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Failed to get system time")
+                    .as_nanos() as u64,
+            )
+        } else {
+            None
+        }
+    })
+}
+
+/// Permutes `tests` according to `shuffle_seed`. The result only depends on
+/// the seed and on the set of test names, so the same order can be replayed
+/// by passing the same seed to a later run of the same test suite, even on
+/// another platform or with another version of the standard library.
+pub fn shuffle_tests(shuffle_seed: u64, tests: &mut [TestDescAndFn]) {
+    let test_names: Vec<&TestName> = tests.iter().map(|test| &test.desc.name).collect();
+    let test_names_hash = calculate_hash(&test_names);
+    let mut rng = Rng::new(shuffle_seed, test_names_hash);
+    shuffle(&mut rng, tests);
+}
+
+/// Fisher-Yates shuffle.
+fn shuffle<T>(rng: &mut Rng, slice: &mut [T]) {
+    for i in 0..slice.len() {
+        let idx = i + rng.rand_range(slice.len() - i);
+        slice.swap(i, idx);
+    }
+}
+
+/// A small deterministic pseudo-random number generator (SplitMix64). It
+/// doesn't have to be of high quality, but its output must not depend on the
+/// platform or the process, which rules out `RandomState`.
+struct Rng {
+    state: u64,
+    extra: u64,
+}
+
+impl Rng {
+    fn new(seed: u64, extra: u64) -> Self {
+        Self { state: seed, extra }
+    }
+
+    /// Returns a number in `0..upper`.
+    fn rand_range(&mut self, upper: usize) -> usize {
+        (self.rand_u64() % upper as u64) as usize
+    }
+
+    fn rand_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state ^ self.extra;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Hashes the bytes of the test names, each followed by a NUL byte.
+///
+/// The hash is computed by hand rather than with `DefaultHasher`, whose output
+/// may change between versions of the standard library, and `Hash`, which
+/// also feeds in lengths of platform-dependent sizes.
+fn calculate_hash(test_names: &[&TestName]) -> u64 {
+    let mut bytes = Vec::new();
+    for name in test_names {
+        bytes.extend_from_slice(name.as_slice().as_bytes());
+        bytes.push(0);
+    }
+    fnv1a(&bytes)
+}
@@ -33,8 +33,12 @@
 
 // Public reexports
 pub use self::bench::{black_box, Bencher};
-pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::console::{run_tests_console, run_tests_console_with_runner};
+pub use self::executor::block_on;
+pub use self::options::{
+    CaptureMode, ColorConfig, Options, OutputFormat, RunIgnored, Shard, ShouldPanic,
+};
+pub use self::runner::Runner;
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -48,8 +52,10 @@ pub mod test {
         cli::{parse_opts, TestOpts},
         filter_tests,
         helpers::metrics::{Metric, MetricMap},
-        options::{Options, RunIgnored, RunStrategy, ShouldPanic},
-        run_test, test_main, test_main_static,
+        options::{CaptureMode, Options, RunIgnored, RunStrategy, Shard, ShouldPanic},
+        run_test,
+        runner::Runner,
+        test_main, test_main_static, test_main_with_runner,
         test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk},
         time::{TestExecTime, TestTimeOptions},
         types::{
@@ -65,7 +71,7 @@ use std::{
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
     sync::mpsc::{channel, Sender},
-    sync::{Arc, Mutex},
+    sync::Arc,
     thread,
     time::{Duration, Instant},
 };
@@ -74,9 +80,11 @@ pub mod bench;
 mod cli;
 mod console;
 mod event;
+mod executor;
 mod formatters;
 mod helpers;
 mod options;
+mod runner;
 pub mod stats;
 mod test_result;
 mod time;
@@ -86,9 +94,11 @@ mod types;
 mod tests;
 
 use event::{CompletedTest, TestEvent};
+use helpers::capture::OutputCapture;
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
-use helpers::sink::Sink;
+use helpers::shard::get_shard_index;
+use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::{Concurrent, RunStrategy};
 use test_result::*;
 use time::TestExecTime;
@@ -101,6 +111,23 @@ const SECONDARY_TEST_INVOKER_VAR: &'static str = "__RUST_TEST_INVOKE";
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Options>) {
+    // If we're being run in SpawnedSecondary mode, run the test here. This
+    // will then exit the process.
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        let test = tests
+            .into_iter()
+            .find(|test| test.desc.name.as_slice() == name)
+            .expect(&format!("couldn't find a test with the provided name '{}'", name));
+        let TestDescAndFn { desc, testfn } = test;
+        let testfn = match testfn {
+            StaticTestFn(f) => f,
+            _ => panic!("only static tests are supported"),
+        };
+        let panic_abort = options.map_or(false, |options| options.panic_abort);
+        run_test_in_spawned_subprocess(desc, Box::new(testfn), panic_abort);
+    }
+
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -129,6 +156,38 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
     }
 }
 
+/// A variant of `test_main` running the tests of a custom runner.
+pub fn test_main_with_runner<R: Runner>(args: &[String], runner: R, tests: Vec<R::Test>) {
+    let opts = match cli::parse_opts(args) {
+        Some(Ok(o)) => o,
+        Some(Err(msg)) => {
+            eprintln!("error: {}", msg);
+            process::exit(ERROR_EXIT_CODE);
+        }
+        None => return,
+    };
+    if let Err(msg) = runner::check_opts(&opts) {
+        eprintln!("error: {}", msg);
+        process::exit(ERROR_EXIT_CODE);
+    }
+    if opts.list {
+        let runner = Arc::new(runner);
+        if let Err(e) = console::list_tests_console(&opts, runner::make_tests(&runner, tests)) {
+            eprintln!("error: io error when listing tests: {:?}", e);
+            process::exit(ERROR_EXIT_CODE);
+        }
+    } else {
+        match console::run_tests_console_with_runner(&opts, runner, tests) {
+            Ok(true) => {}
+            Ok(false) => process::exit(ERROR_EXIT_CODE),
+            Err(e) => {
+                eprintln!("error: io error when running tests: {:?}", e);
+                process::exit(ERROR_EXIT_CODE);
+            }
+        }
+    }
+}
+
 /// A variant optimized for invocation with a static test vector.
 /// This will panic (intentionally) when fed any dynamic tests.
 ///
@@ -149,24 +208,6 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
-    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
-        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
-        let test = tests
-            .iter()
-            .filter(|test| test.desc.name.as_slice() == name)
-            .map(make_owned_test)
-            .next()
-            .expect(&format!("couldn't find a test with the provided name '{}'", name));
-        let TestDescAndFn { desc, testfn } = test;
-        let testfn = match testfn {
-            StaticTestFn(f) => f,
-            _ => panic!("only static tests are supported"),
-        };
-        run_test_in_spawned_subprocess(desc, Box::new(testfn));
-    }
-
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
     test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
@@ -228,13 +269,23 @@ where
         filtered_tests
     };
 
+    let shuffle_seed = get_shuffle_seed(opts);
+    let filtered_tests = {
+        let mut filtered_tests = filtered_tests;
+        if let Some(shuffle_seed) = shuffle_seed {
+            shuffle_tests(shuffle_seed, &mut filtered_tests);
+        }
+
+        filtered_tests
+    };
+
     let filtered_out = tests_len - filtered_tests.len();
     let event = TestEvent::TeFilteredOut(filtered_out);
     notify_about_test_event(event)?;
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    let event = TestEvent::TeFiltered(filtered_descs);
+    let event = TestEvent::TeFiltered(filtered_descs, shuffle_seed);
     notify_about_test_event(event)?;
 
     let (filtered_tests, filtered_benchs): (Vec<_>, _) =
@@ -250,7 +301,7 @@ where
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if (opts.options.panic_abort && !opts.force_run_in_process) || opts.isolate {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
@@ -258,6 +309,19 @@ where
 
     let mut running_tests: TestMap = HashMap::default();
 
+    // Failed tests can only be run again if their function can be called more
+    // than once, so remember the functions of the tests that may be retried.
+    let mut retryable_tests: HashMap<TestDesc, fn()> = HashMap::new();
+    if opts.retries > 0 {
+        for test in &remaining {
+            if let StaticTestFn(f) = test.testfn {
+                retryable_tests.insert(test.desc.clone(), f);
+            }
+        }
+    }
+    // The number of retries so far and the output of the failed attempts.
+    let mut retried_tests: HashMap<TestDesc, (usize, Vec<u8>)> = HashMap::new();
+
     fn get_timed_out_tests(running_tests: &mut TestMap) -> Vec<TestDesc> {
         let now = Instant::now();
         let timed_out = running_tests
@@ -277,16 +341,68 @@ where
         })
     };
 
+    // Puts a failed test back into the queue if it has retries left, in which
+    // case `None` is returned and the result must not be reported.
+    fn retry_failed_test(
+        opts: &TestOpts,
+        retryable_tests: &HashMap<TestDesc, fn()>,
+        retried_tests: &mut HashMap<TestDesc, (usize, Vec<u8>)>,
+        remaining: &mut Vec<TestDescAndFn>,
+        mut completed_test: CompletedTest,
+    ) -> Option<CompletedTest> {
+        let failed = match completed_test.result {
+            TrFailed | TrFailedMsg(_) | TrTimedFail => true,
+            _ => false,
+        };
+        let desc = &completed_test.desc;
+
+        if let (true, Some(&f)) = (failed, retryable_tests.get(desc)) {
+            let (retries, stdout) = retried_tests.entry(desc.clone()).or_default();
+            if *retries < opts.retries {
+                *retries += 1;
+                stdout.extend_from_slice(&completed_test.stdout);
+                if let TrFailedMsg(ref msg) = completed_test.result {
+                    stdout.extend_from_slice(format!("note: {}\n", msg).as_bytes());
+                }
+                stdout.extend_from_slice(
+                    format!("note: test failed, retrying ({} of {})\n", retries, opts.retries)
+                        .as_bytes(),
+                );
+                remaining.push(TestDescAndFn { desc: desc.clone(), testfn: StaticTestFn(f) });
+                return None;
+            }
+        }
+
+        if let Some((retries, mut stdout)) = retried_tests.remove(desc) {
+            if completed_test.result == TrOk {
+                completed_test.result = TrFlaky(retries);
+            }
+            stdout.extend_from_slice(&completed_test.stdout);
+            completed_test.stdout = stdout;
+        }
+        Some(completed_test)
+    }
+
     if concurrency == 1 {
         while !remaining.is_empty() {
             let test = remaining.pop().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            if !retried_tests.contains_key(&test.desc) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
             run_test(opts, !opts.run_tests, test, run_strategy, tx.clone(), Concurrent::No);
             let completed_test = rx.recv().unwrap();
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
+            if let Some(completed_test) = retry_failed_test(
+                opts,
+                &retryable_tests,
+                &mut retried_tests,
+                &mut remaining,
+                completed_test,
+            ) {
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+            }
         }
     } else {
         while pending > 0 || !remaining.is_empty() {
@@ -295,8 +411,10 @@ where
                 let timeout = time::get_default_test_timeout();
                 running_tests.insert(test.desc.clone(), timeout);
 
-                let event = TestEvent::TeWait(test.desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                if !retried_tests.contains_key(&test.desc) {
+                    let event = TestEvent::TeWait(test.desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
                 run_test(opts, !opts.run_tests, test, run_strategy, tx.clone(), Concurrent::Yes);
                 pending += 1;
             }
@@ -327,10 +445,18 @@ where
 
             let completed_test = res.unwrap();
             running_tests.remove(&completed_test.desc);
-
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
             pending -= 1;
+
+            if let Some(completed_test) = retry_failed_test(
+                opts,
+                &retryable_tests,
+                &mut retried_tests,
+                &mut remaining,
+                completed_test,
+            ) {
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+            }
         }
     }
 
@@ -373,6 +499,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         filtered.retain(|test| test.desc.should_panic == ShouldPanic::No);
     }
 
+    // Only keep the tests belonging to the requested shard
+    if let Some(shard) = opts.shard {
+        filtered.retain(|test| get_shard_index(&test.desc.name, shard.count) == shard.index);
+    }
+
     // maybe unignore tests
     match opts.run_ignored {
         RunIgnored::Yes => {
@@ -434,8 +565,10 @@ pub fn run_test(
     struct TestRunOpts {
         pub strategy: RunStrategy,
         pub nocapture: bool,
+        pub capture: CaptureMode,
         pub concurrency: Concurrent,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
     }
 
     fn run_test_inner(
@@ -450,11 +583,12 @@ pub fn run_test(
         let runtest = move || match opts.strategy {
             RunStrategy::InProcess => run_test_in_process(
                 desc,
-                opts.nocapture,
+                if opts.nocapture { None } else { Some(opts.capture) },
                 opts.time.is_some(),
                 testfn,
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
             RunStrategy::SpawnPrimary => spawn_test_subprocess(
                 desc,
@@ -462,6 +596,7 @@ pub fn run_test(
                 opts.time.is_some(),
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -477,8 +612,19 @@ pub fn run_test(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    let mut test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        // The file descriptors are shared by the whole process, so they can't
+        // be redirected while several tests are running.
+        capture: match (opts.capture, concurrency) {
+            (CaptureMode::Fd, Concurrent::Yes) => CaptureMode::Inherit,
+            (capture, _) => capture,
+        },
+        concurrency,
+        time: opts.time_options,
+        timeout: desc.timeout.or(opts.test_timeout),
+    };
 
     match testfn {
         DynBenchFn(bencher) => {
@@ -494,6 +640,12 @@ pub fn run_test(
         DynTestFn(f) => {
             match strategy {
                 RunStrategy::InProcess => (),
+                // A spawned process has no way of finding a dynamic test, so
+                // those are exempt from `--isolate` as long as the process can
+                // survive a failing test.
+                RunStrategy::SpawnPrimary if !opts.options.panic_abort => {
+                    test_run_opts.strategy = RunStrategy::InProcess;
+                }
                 _ => panic!("Cannot run dynamic test fn out-of-process"),
             };
             run_test_inner(
@@ -520,41 +672,47 @@ fn __rust_begin_short_backtrace<F: FnOnce()>(f: F) {
 
 fn run_test_in_process(
     desc: TestDesc,
-    nocapture: bool,
+    capture: Option<CaptureMode>,
     report_time: bool,
     testfn: Box<dyn FnOnce() + Send>,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
-    // Buffer for capturing standard I/O
-    let data = Arc::new(Mutex::new(Vec::new()));
-
-    let oldio = if !nocapture {
-        Some((
-            io::set_print(Some(Sink::new_boxed(&data))),
-            io::set_panic(Some(Sink::new_boxed(&data))),
-        ))
-    } else {
-        None
-    };
+    let output_capture = capture.map(OutputCapture::start);
 
-    let start = report_time.then(Instant::now);
+    executor::set_test_timeout(timeout);
+    let start = Instant::now();
     let result = catch_unwind(AssertUnwindSafe(testfn));
-    let exec_time = start.map(|start| {
-        let duration = start.elapsed();
-        TestExecTime(duration)
-    });
+    let duration = start.elapsed();
+    executor::set_test_timeout(None);
+    let exec_time = report_time.then(|| TestExecTime(duration));
 
-    if let Some((printio, panicio)) = oldio {
-        io::set_print(printio);
-        io::set_panic(panicio);
-    }
+    let mut stdout = output_capture.map(OutputCapture::finish).unwrap_or_default();
 
+    let timed_out = match result {
+        Err(ref e) => executor::is_timed_out(e.as_ref()),
+        Ok(()) => false,
+    };
     let test_result = match result {
         Ok(()) => calc_result(&desc, Ok(()), &time_opts, &exec_time),
         Err(e) => calc_result(&desc, Err(e.as_ref()), &time_opts, &exec_time),
     };
-    let stdout = data.lock().unwrap().to_vec();
+
+    // A test running in-process can only be interrupted by the executor of
+    // async tests, but it still shouldn't pass if it took longer than it was
+    // allowed to.
+    let test_result = match timeout {
+        Some(timeout) if timed_out => {
+            write_timeout_message(&mut stdout, timeout, None);
+            TrTimedFail
+        }
+        Some(timeout) if test_result == TrOk && duration > timeout => {
+            write_timeout_message(&mut stdout, timeout, Some(duration));
+            TrTimedFail
+        }
+        _ => test_result,
+    };
     let message = CompletedTest::new(desc, test_result, exec_time, stdout);
     monitor_ch.send(message).unwrap();
 }
@@ -565,6 +723,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -578,7 +737,11 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => output_with_timeout(&mut command, nocapture, timeout),
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -595,6 +758,11 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if let (true, Some(timeout)) = (timed_out, timeout) {
+            write_timeout_message(&mut test_output, timeout, None);
+            return (TrTimedFail, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -613,7 +781,94 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
+/// Runs `command` to completion like `Command::output` does, but kills it if
+/// it doesn't exit within `timeout`. The returned flag is `true` if the child
+/// had to be killed.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+
+    // The pipes have to be drained while waiting, otherwise a child writing
+    // a lot of output would block forever and always hit the timeout.
+    fn read_to_end_in_thread<R>(mut pipe: R) -> thread::JoinHandle<Vec<u8>>
+    where
+        R: io::Read + Send + 'static,
+    {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            buf
+        })
+    }
+    let stdout = child.stdout.take().map(read_to_end_in_thread);
+    let stderr = child.stderr.take().map(read_to_end_in_thread);
+
+    let deadline = Instant::now() + timeout;
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        if Instant::now() >= deadline {
+            // The child may exit on its own in the meantime, in which case
+            // `kill` fails but `wait` still collects its status.
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = stdout.map(|t| t.join().unwrap()).unwrap_or_default();
+    let stderr = stderr.map(|t| t.join().unwrap()).unwrap_or_default();
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
+/// Explains why a test failed because of its timeout: either it was terminated
+/// when it reached its timeout, or it completed in `duration`, after it.
+fn write_timeout_message(test_output: &mut Vec<u8>, timeout: Duration, duration: Option<Duration>) {
+    match test_output.last() {
+        Some(b'\n') | None => (),
+        Some(_) => test_output.push(b'\n'),
+    }
+    match duration {
+        None => write!(
+            test_output,
+            "note: test was terminated after running for {}, its time limit",
+            TestExecTime(timeout)
+        ),
+        Some(duration) => write!(
+            test_output,
+            "note: test completed in {}, which exceeds its time limit of {}",
+            TestExecTime(duration),
+            TestExecTime(timeout)
+        ),
+    }
+    .unwrap();
+}
+
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    testfn: Box<dyn FnOnce() + Send>,
+    panic_abort: bool,
+) -> ! {
+    if !panic_abort {
+        // Panics unwind, so the test may catch them itself, e.g. with
+        // `catch_unwind` or by joining a thread, and `should_panic` tests
+        // get to run their cleanup. The result is only known once the test
+        // function has returned.
+        let result = catch_unwind(AssertUnwindSafe(testfn));
+        let result = result.as_ref().map(|_| ()).map_err(|e| &**e);
+        let test_result = calc_result(&desc, result, &None, &None);
+        print_failure_message(&test_result);
+        exit_with_test_result(&test_result);
+    }
+
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
         let test_result = match panic_info {
@@ -621,21 +876,13 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
             None => calc_result(&desc, Ok(()), &None, &None),
         };
 
-        // We don't support serializing TrFailedMsg, so just
-        // print the message out to stderr.
-        if let TrFailedMsg(msg) = &test_result {
-            eprintln!("{}", msg);
-        }
+        print_failure_message(&test_result);
 
         if let Some(info) = panic_info {
             builtin_panic_hook(info);
         }
 
-        if let TrOk = test_result {
-            process::exit(test_result::TR_OK);
-        } else {
-            process::exit(test_result::TR_FAILED);
-        }
+        exit_with_test_result(&test_result);
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(&info))));
@@ -643,3 +890,19 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
     record_result(None);
     unreachable!("panic=abort callback should have exited the process")
 }
+
+fn print_failure_message(test_result: &TestResult) {
+    // We don't support serializing TrFailedMsg, so just
+    // print the message out to stderr.
+    if let TrFailedMsg(msg) = test_result {
+        eprintln!("{}", msg);
+    }
+}
+
+fn exit_with_test_result(test_result: &TestResult) -> ! {
+    if let TrOk = test_result {
+        process::exit(test_result::TR_OK);
+    } else {
+        process::exit(test_result::TR_FAILED);
+    }
+}
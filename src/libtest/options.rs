@@ -39,6 +39,22 @@ pub enum OutputFormat {
     Terse,
     /// JSON output
     Json,
+    /// JUnit output
+    Junit,
+}
+
+/// How the output of tests running in-process is captured
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Capture the output of the test's own thread
+    Local,
+    /// Also capture the output of the threads spawned by the test
+    Inherit,
+    /// Redirect the standard output and error file descriptors of the process
+    /// while the test runs, which also captures the output of child processes
+    /// and foreign code. Only used when tests run one at a time, otherwise
+    /// this behaves like `Inherit`.
+    Fd,
 }
 
 /// Whether ignored test should be runned or not
@@ -50,6 +66,16 @@ pub enum RunIgnored {
     Only,
 }
 
+/// Part of the test suite to run when it is split across several processes or
+/// machines. Every test belongs to exactly one of the `count` shards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// Zero-based index of the shard to run.
+    pub index: usize,
+    /// Total number of shards.
+    pub count: usize,
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
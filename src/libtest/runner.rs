@@ -0,0 +1,64 @@
+//! Support for test runners that supply their own kinds of tests.
+//!
+//! A custom test framework can implement `Runner` to describe and run its
+//! tests, e.g. tests with fixtures or parameterized tests, while libtest takes
+//! care of filtering, running the tests concurrently, capturing their output
+//! and reporting their results in the requested format.
+
+use std::sync::Arc;
+
+use super::cli::TestOpts;
+use super::test_result::TestResult;
+use super::types::{TestDesc, TestDescAndFn, TestFn};
+
+/// A test runner supplying its own kind of tests.
+pub trait Runner: Send + Sync + 'static {
+    /// The tests run by this runner.
+    type Test: Send + 'static;
+
+    /// Describes a test. The description is used to filter the tests and to
+    /// report their results, so every test should have a unique name.
+    fn describe(&self, test: &Self::Test) -> TestDesc;
+
+    /// Runs a test. It is called on the thread the test runs on, with its
+    /// output captured unless `--nocapture` is given.
+    ///
+    /// The test fails if an error is returned or if it panics.
+    fn run(&self, test: Self::Test) -> Result<(), String>;
+
+    /// Called with the result of every test that was run, before it is
+    /// reported.
+    fn report(&self, _desc: &TestDesc, _result: &TestResult) {}
+}
+
+/// Checks that the options can be honored for the tests of a custom runner.
+///
+/// These tests are only known to the runner and are consumed by running them,
+/// so they can neither be found again in a subprocess (`--isolate`) nor run
+/// again (`--retries`).
+pub(crate) fn check_opts(opts: &TestOpts) -> Result<(), String> {
+    if opts.isolate {
+        return Err("the option --isolate is not supported by custom test runners".into());
+    }
+    if opts.retries > 0 {
+        return Err("the option --retries is not supported by custom test runners".into());
+    }
+    Ok(())
+}
+
+/// Turns the tests of a runner into tests that can be run by libtest.
+pub(crate) fn make_tests<R: Runner>(runner: &Arc<R>, tests: Vec<R::Test>) -> Vec<TestDescAndFn> {
+    tests
+        .into_iter()
+        .map(|test| {
+            let desc = runner.describe(&test);
+            let runner = runner.clone();
+            let testfn = TestFn::DynTestFn(Box::new(move || {
+                if let Err(msg) = runner.run(test) {
+                    panic!("{}", msg);
+                }
+            }));
+            TestDescAndFn { desc, testfn }
+        })
+        .collect()
+}
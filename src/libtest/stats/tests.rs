@@ -572,6 +572,23 @@ fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
 
+#[test]
+fn test_mann_whitney_u_test() {
+    let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+    let b = [6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0];
+    assert_approx_eq!(mann_whitney_u_test(&a, &b), 0.004510263631180416);
+    assert_approx_eq!(mann_whitney_u_test(&b, &a), 0.004510263631180416);
+    assert_approx_eq!(mann_whitney_u_test(&a, &a), 1.0);
+
+    // With ties.
+    let a = [1.0, 2.0, 2.0, 3.0];
+    let b = [2.0, 3.0, 3.0, 4.0, 5.0];
+    assert_approx_eq!(mann_whitney_u_test(&a, &b), 0.07592696298255767);
+
+    assert_eq!(mann_whitney_u_test(&[1.0, 1.0], &[1.0]), 1.0);
+    assert_eq!(mann_whitney_u_test(&[], &[1.0]), 1.0);
+}
+
 #[bench]
 pub fn sum_three_items(b: &mut Bencher) {
     b.iter(|| {
@@ -22,6 +22,8 @@ pub enum TestResult {
     TrAllowedFail,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test failed at first, but passed after the given number of retries.
+    TrFlaky(usize),
 }
 
 unsafe impl Send for TestResult {}
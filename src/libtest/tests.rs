@@ -3,7 +3,7 @@ use super::*;
 use crate::{
     bench::Bencher,
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{JunitFormatter, OutputFormatter, PrettyFormatter},
     options::OutputFormat,
     test::{
         filter_tests,
@@ -14,6 +14,7 @@ use crate::{
         MetricMap,
         RunIgnored,
         RunStrategy,
+        Shard,
         ShouldPanic,
         StaticTestName,
         TestDesc,
@@ -38,17 +39,26 @@ impl TestOpts {
             filter: None,
             filter_exact: false,
             force_run_in_process: false,
+            isolate: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
             run_tests: false,
             bench_benchmarks: false,
+            save_baseline: None,
+            baseline: None,
             logfile: None,
             nocapture: false,
+            capture: CaptureMode::Local,
             color: AutoColor,
             format: OutputFormat::Pretty,
             test_threads: None,
+            test_timeout: None,
             skip: vec![],
+            shard: None,
+            retries: 0,
             time_options: None,
+            shuffle: false,
+            shuffle_seed: None,
             options: Options::new(),
         }
     }
@@ -63,6 +73,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move || {})),
         },
@@ -73,6 +84,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 should_panic: ShouldPanic::No,
                 allow_fail: false,
                 test_type: TestType::Unknown,
+                timeout: None,
             },
             testfn: DynTestFn(Box::new(move || {})),
         },
@@ -91,6 +103,7 @@ pub fn do_not_run_ignored_tests() {
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -110,6 +123,7 @@ pub fn ignored_tests_result_in_ignored() {
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -133,6 +147,7 @@ fn test_should_panic() {
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -156,6 +171,7 @@ fn test_should_panic_good_message() {
             should_panic: ShouldPanic::YesWithMessage("error message"),
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -184,6 +200,7 @@ fn test_should_panic_bad_message() {
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -215,6 +232,7 @@ fn test_should_panic_non_string_message_type() {
             should_panic: ShouldPanic::YesWithMessage(expected),
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -236,6 +254,7 @@ fn test_should_panic_but_succeeds() {
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -254,6 +273,7 @@ fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -287,6 +307,7 @@ fn time_test_failure_template(test_type: TestType) -> TestResult {
             should_panic: ShouldPanic::No,
             allow_fail: false,
             test_type,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -317,6 +338,164 @@ fn test_error_on_exceed() {
     assert_eq!(result, TestResult::TrOk);
 }
 
+fn timeout_test_template(timeout: Duration) -> TestResult {
+    fn f() {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+            timeout: Some(timeout),
+        },
+        testfn: DynTestFn(Box::new(f)),
+    };
+    let (tx, rx) = channel();
+    run_test(&TestOpts::new(), false, desc, RunStrategy::InProcess, tx, Concurrent::No);
+    rx.recv().unwrap().result
+}
+
+#[test]
+fn test_in_process_timeout() {
+    assert_eq!(timeout_test_template(Duration::from_millis(1)), TestResult::TrTimedFail);
+    assert_eq!(timeout_test_template(Duration::from_secs(60)), TestResult::TrOk);
+}
+
+#[test]
+fn test_block_on() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    // Pending until woken up by another thread.
+    struct WokenByThread(bool);
+    impl Future for WokenByThread {
+        type Output = u32;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            if self.0 {
+                return Poll::Ready(42);
+            }
+            self.0 = true;
+            let waker = cx.waker().clone();
+            std::thread::spawn(move || waker.wake());
+            Poll::Pending
+        }
+    }
+
+    // Pending, without keeping its waker.
+    struct Forgotten;
+    impl Future for Forgotten {
+        type Output = ();
+        fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            Poll::Pending
+        }
+    }
+
+    // Pending, keeping its waker without ever using it.
+    struct Stalled(Option<Waker>);
+    impl Future for Stalled {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.0 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    assert_eq!(block_on(WokenByThread(false)), 42);
+    assert!(catch_unwind(|| block_on(Forgotten)).is_err());
+
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+            timeout: Some(Duration::from_millis(10)),
+        },
+        testfn: DynTestFn(Box::new(|| block_on(Stalled(None)))),
+    };
+    let (tx, rx) = channel();
+    run_test(&TestOpts::new(), false, desc, RunStrategy::InProcess, tx, Concurrent::No);
+    assert_eq!(rx.recv().unwrap().result, TestResult::TrTimedFail);
+}
+
+fn retry_test_template(f: fn(), retries: usize) -> CompletedTest {
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+            timeout: None,
+        },
+        testfn: StaticTestFn(f),
+    };
+    let mut opts = TestOpts::new();
+    opts.retries = retries;
+    opts.run_tests = true;
+    opts.test_threads = Some(1);
+
+    let mut results = Vec::new();
+    run_tests(&opts, vec![desc], |event| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push(completed_test);
+        }
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(results.len(), 1);
+    results.pop().unwrap()
+}
+
+#[test]
+fn test_retries() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    fn flaky() {
+        assert!(ATTEMPTS.fetch_add(1, Ordering::SeqCst) >= 2);
+    }
+    fn failing() {
+        panic!();
+    }
+
+    let completed_test = retry_test_template(flaky, 3);
+    assert_eq!(completed_test.result, TestResult::TrFlaky(2));
+    let stdout = String::from_utf8(completed_test.stdout).unwrap();
+    assert!(stdout.contains("note: test failed, retrying (2 of 3)"));
+
+    assert_eq!(retry_test_template(failing, 2).result, TestResult::TrFailed);
+    assert_eq!(retry_test_template(failing, 0).result, TestResult::TrFailed);
+}
+
+#[test]
+fn test_capture_inherited_by_spawned_threads() {
+    let desc = TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName("whatever"),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+            timeout: None,
+        },
+        testfn: DynTestFn(Box::new(|| {
+            std::thread::spawn(|| println!("hello from a spawned thread")).join().unwrap();
+        })),
+    };
+    let mut opts = TestOpts::new();
+    opts.capture = CaptureMode::Inherit;
+    let (tx, rx) = channel();
+    run_test(&opts, false, desc, RunStrategy::InProcess, tx, Concurrent::No);
+    let stdout = String::from_utf8(rx.recv().unwrap().stdout).unwrap();
+    assert_eq!(stdout, "hello from a spawned thread\n");
+}
+
 fn typed_test_desc(test_type: TestType) -> TestDesc {
     TestDesc {
         name: StaticTestName("whatever"),
@@ -324,6 +503,7 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type,
+        timeout: None,
     }
 }
 
@@ -380,6 +560,81 @@ fn parse_show_output_flag() {
     assert!(opts.options.display_output);
 }
 
+#[test]
+fn parse_test_timeout_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "0".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_retries_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries".to_string(),
+        "3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 3);
+
+    let args = vec!["progname".to_string(), "--retries".to_string(), "3".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_capture_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--capture".to_string(),
+        "inherit".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.capture, CaptureMode::Inherit);
+
+    let args = vec!["progname".to_string(), "--capture".to_string(), "fd".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--capture".to_string(),
+        "fd".to_string(),
+        "--nocapture".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_isolate_flag() {
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--isolate".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.isolate);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate".to_string(),
+        "--force-run-in-process".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 fn parse_include_ignored_flag() {
     let args = vec![
@@ -440,6 +695,7 @@ pub fn exclude_should_panic_option() {
             should_panic: ShouldPanic::Yes,
             allow_fail: false,
             test_type: TestType::Unknown,
+            timeout: None,
         },
         testfn: DynTestFn(Box::new(move || {})),
     });
@@ -462,6 +718,7 @@ pub fn exact_filter_match() {
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
                     test_type: TestType::Unknown,
+                    timeout: None,
                 },
                 testfn: DynTestFn(Box::new(move || {})),
             })
@@ -537,6 +794,7 @@ pub fn sort_tests() {
                     should_panic: ShouldPanic::No,
                     allow_fail: false,
                     test_type: TestType::Unknown,
+                    timeout: None,
                 },
                 testfn: DynTestFn(Box::new(testfn)),
             };
@@ -565,6 +823,131 @@ pub fn sort_tests() {
     }
 }
 
+#[test]
+pub fn shuffle_tests_with_seed() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..20)
+            .map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("test::{:02}", i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                    test_type: TestType::Unknown,
+                    timeout: None,
+                },
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    }
+    fn names(tests: &[TestDescAndFn]) -> Vec<String> {
+        tests.iter().map(|test| test.desc.name.to_string()).collect()
+    }
+
+    let sorted = names(&tests());
+
+    let mut shuffled = tests();
+    helpers::shuffle::shuffle_tests(42, &mut shuffled);
+    let shuffled = names(&shuffled);
+    assert_ne!(shuffled, sorted);
+
+    let mut sorted_again = shuffled.clone();
+    sorted_again.sort();
+    assert_eq!(sorted_again, sorted);
+
+    // The same seed must always produce the same order, on every platform
+    // and with every version of the standard library.
+    let mut replayed = tests();
+    helpers::shuffle::shuffle_tests(42, &mut replayed);
+    assert_eq!(names(&replayed), shuffled);
+    let expected: Vec<_> = [14, 16, 19, 12, 17, 5, 18, 2, 13, 8, 3, 0, 11, 1, 4, 6, 7, 10, 15, 9]
+        .iter()
+        .map(|i| format!("test::{:02}", i))
+        .collect();
+    assert_eq!(shuffled, expected);
+
+    let mut other = tests();
+    helpers::shuffle::shuffle_tests(43, &mut other);
+    assert_ne!(names(&other), shuffled);
+}
+
+#[test]
+pub fn filter_tests_by_shard() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..50)
+            .map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("test::{:02}", i)),
+                    ignore: false,
+                    should_panic: ShouldPanic::No,
+                    allow_fail: false,
+                    test_type: TestType::Unknown,
+                    timeout: None,
+                },
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    }
+
+    let count = 3;
+    let mut seen = Vec::new();
+    for index in 0..count {
+        let mut opts = TestOpts::new();
+        opts.shard = Some(Shard { index, count });
+        let filtered = filter_tests(&opts, tests());
+        assert!(!filtered.is_empty());
+        seen.extend(filtered.into_iter().map(|test| test.desc.name.to_string()));
+    }
+
+    // Every test must be run by exactly one shard.
+    seen.sort();
+    let all: Vec<_> = tests().into_iter().map(|test| test.desc.name.to_string()).collect();
+    assert_eq!(seen, all);
+}
+
+#[test]
+fn parse_shard_flags() {
+    let args = |index: &str, count: &str| {
+        vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--shard-index".to_string(),
+            index.to_string(),
+            "--shard-count".to_string(),
+            count.to_string(),
+        ]
+    };
+    let opts = parse_opts(&args("1", "4")).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(Shard { index: 1, count: 4 }));
+
+    assert!(parse_opts(&args("4", "4")).unwrap().is_err());
+    assert!(parse_opts(&args("0", "0")).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard-count".to_string(),
+        "4".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_shuffle_seed_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shuffle-seed".to_string(),
+        "123".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shuffle_seed, Some(123));
+    assert_eq!(helpers::shuffle::get_shuffle_seed(&opts), Some(123));
+
+    let args = vec!["progname".to_string(), "--shuffle".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -614,6 +997,7 @@ pub fn test_bench_no_iter() {
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     crate::bench::benchmark(desc, tx, true, f);
@@ -634,6 +1018,7 @@ pub fn test_bench_iter() {
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     crate::bench::benchmark(desc, tx, true, f);
@@ -648,6 +1033,7 @@ fn should_sort_failures_before_printing_them() {
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     let test_b = TestDesc {
@@ -656,6 +1042,7 @@ fn should_sort_failures_before_printing_them() {
         should_panic: ShouldPanic::No,
         allow_fail: false,
         test_type: TestType::Unknown,
+        timeout: None,
     };
 
     let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
@@ -667,6 +1054,7 @@ fn should_sort_failures_before_printing_them() {
         failed: 0,
         ignored: 0,
         allowed_fail: 0,
+        flaky: 0,
         filtered_out: 0,
         measured: 0,
         metrics: MetricMap::new(),
@@ -674,6 +1062,8 @@ fn should_sort_failures_before_printing_them() {
         options: Options::new(),
         not_failures: Vec::new(),
         time_failures: Vec::new(),
+        benchmarks: Vec::new(),
+        baseline: None,
     };
 
     out.write_failures(&st).unwrap();
@@ -686,3 +1076,268 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+#[test]
+fn run_tests_of_custom_runner() {
+    use crate::runner::{make_tests, Runner};
+    use std::sync::Arc;
+
+    // Runs the same check with several parameters.
+    struct ParamRunner;
+
+    impl Runner for ParamRunner {
+        type Test = u32;
+
+        fn describe(&self, param: &u32) -> TestDesc {
+            TestDesc {
+                name: DynTestName(format!("is_even::{}", param)),
+                ignore: false,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+                test_type: TestType::Unknown,
+                timeout: None,
+            }
+        }
+
+        fn run(&self, param: u32) -> Result<(), String> {
+            if param % 2 == 0 { Ok(()) } else { Err(format!("{} is odd", param)) }
+        }
+    }
+
+    let mut opts = TestOpts::new();
+    opts.run_tests = true;
+    opts.skip = vec!["is_even::4".to_string()];
+
+    let tests = make_tests(&Arc::new(ParamRunner), vec![1, 2, 3, 4]);
+    let mut results = Vec::new();
+    run_tests(&opts, tests, |event| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push((completed_test.desc.name.to_string(), completed_test.result));
+        }
+        Ok(())
+    })
+    .unwrap();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        results,
+        vec![
+            ("is_even::1".to_string(), TestResult::TrFailed),
+            ("is_even::2".to_string(), TestResult::TrOk),
+            ("is_even::3".to_string(), TestResult::TrFailed),
+        ]
+    );
+}
+
+#[test]
+fn custom_runner_rejects_isolate_and_retries() {
+    use crate::runner::check_opts;
+
+    let mut opts = TestOpts::new();
+    assert!(check_opts(&opts).is_ok());
+    opts.isolate = true;
+    assert!(check_opts(&opts).unwrap_err().contains("--isolate"));
+    opts.isolate = false;
+    opts.retries = 2;
+    assert!(check_opts(&opts).unwrap_err().contains("--retries"));
+}
+
+#[test]
+fn report_results_of_custom_runner() {
+    use crate::runner::Runner;
+    use std::sync::{Arc, Mutex};
+
+    // Runs plain test functions and collects their results.
+    struct FnRunner {
+        reported: Arc<Mutex<Vec<(String, TestResult)>>>,
+    }
+
+    impl Runner for FnRunner {
+        type Test = (&'static str, fn());
+
+        fn describe(&self, &(name, _): &(&'static str, fn())) -> TestDesc {
+            TestDesc {
+                name: StaticTestName(name),
+                ignore: false,
+                should_panic: ShouldPanic::No,
+                allow_fail: false,
+                test_type: TestType::Unknown,
+                timeout: None,
+            }
+        }
+
+        fn run(&self, (_, f): (&'static str, fn())) -> Result<(), String> {
+            f();
+            Ok(())
+        }
+
+        fn report(&self, desc: &TestDesc, result: &TestResult) {
+            self.reported.lock().unwrap().push((desc.name.to_string(), result.clone()));
+        }
+    }
+
+    fn passes() {}
+    fn fails() {
+        panic!("fails");
+    }
+
+    let mut opts = TestOpts::new();
+    opts.run_tests = true;
+    opts.test_threads = Some(1);
+    opts.format = OutputFormat::Terse;
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let runner = FnRunner { reported: reported.clone() };
+    let tests: Vec<(&'static str, fn())> = vec![("passes", passes), ("fails", fails)];
+    let passed = crate::run_tests_console_with_runner(&opts, runner, tests).unwrap();
+
+    assert!(!passed);
+    let mut reported = reported.lock().unwrap().clone();
+    reported.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        reported,
+        vec![("fails".to_string(), TestResult::TrFailed), ("passes".to_string(), TestResult::TrOk)]
+    );
+}
+
+#[test]
+fn compare_bench_to_baseline() {
+    use crate::bench::BenchSamples;
+    use crate::helpers::baseline::Baseline;
+    use crate::stats::Summary;
+    use std::collections::HashMap;
+
+    fn bench_samples(base: f64) -> BenchSamples {
+        let samples: Vec<f64> = (0..50).map(|i| base + (i % 10) as f64).collect();
+        BenchSamples { ns_iter_summ: Summary::new(&samples), mb_s: 0, samples }
+    }
+
+    let mut benches = HashMap::new();
+    benches.insert("bench".to_string(), bench_samples(1000.0).samples);
+    let baseline = Baseline::new(benches);
+
+    let unchanged = baseline.compare("bench", &bench_samples(1000.0)).unwrap();
+    assert_eq!(unchanged.change_pct, 0.0);
+    assert!(!unchanged.is_regression() && !unchanged.is_improvement());
+
+    let slower = baseline.compare("bench", &bench_samples(1100.0)).unwrap();
+    assert!(slower.change_pct > 9.0);
+    assert!(slower.p_value < 0.001);
+    assert!(slower.is_regression());
+
+    let faster = baseline.compare("bench", &bench_samples(900.0)).unwrap();
+    assert!(faster.is_improvement());
+
+    assert_eq!(baseline.compare("other", &bench_samples(1000.0)), None);
+}
+
+#[test]
+fn merge_bench_results_into_baseline() {
+    use crate::bench::BenchSamples;
+    use crate::helpers::baseline::Baseline;
+    use crate::stats::Summary;
+    use std::collections::HashMap;
+
+    fn bench_samples(base: f64) -> BenchSamples {
+        let samples: Vec<f64> = (0..50).map(|i| base + (i % 10) as f64).collect();
+        BenchSamples { ns_iter_summ: Summary::new(&samples), mb_s: 0, samples }
+    }
+
+    fn bench_desc(name: &'static str) -> TestDesc {
+        TestDesc {
+            name: StaticTestName(name),
+            ignore: false,
+            should_panic: ShouldPanic::No,
+            allow_fail: false,
+            test_type: TestType::Unknown,
+            timeout: None,
+        }
+    }
+
+    let mut benches = HashMap::new();
+    benches.insert("kept".to_string(), bench_samples(1000.0).samples);
+    benches.insert("updated".to_string(), bench_samples(1000.0).samples);
+    let mut baseline = Baseline::new(benches);
+
+    baseline.merge(&[(bench_desc("updated"), bench_samples(2000.0))]);
+
+    let kept = baseline.compare("kept", &bench_samples(1000.0)).unwrap();
+    assert_eq!(kept.change_pct, 0.0);
+    let updated = baseline.compare("updated", &bench_samples(2000.0)).unwrap();
+    assert_eq!(updated.change_pct, 0.0);
+}
+
+#[test]
+fn junit_output_escapes_and_reports_failures() {
+    let test_ok = TestDesc {
+        name: StaticTestName("module::ok"),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+
+    let test_failed = TestDesc {
+        name: StaticTestName("module::<failed>"),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: false,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+
+    let test_allowed_fail = TestDesc {
+        name: StaticTestName("module::allowed_fail"),
+        ignore: false,
+        should_panic: ShouldPanic::No,
+        allow_fail: true,
+        test_type: TestType::Unknown,
+        timeout: None,
+    };
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+
+    let st = console::ConsoleTestState {
+        log_out: None,
+        total: 3,
+        passed: 1,
+        failed: 1,
+        ignored: 0,
+        allowed_fail: 1,
+        flaky: 0,
+        filtered_out: 0,
+        measured: 0,
+        metrics: MetricMap::new(),
+        failures: Vec::new(),
+        options: Options::new(),
+        not_failures: Vec::new(),
+        time_failures: Vec::new(),
+        benchmarks: Vec::new(),
+        baseline: None,
+    };
+
+    let exec_time = test_exec_time(1500);
+    out.write_run_start(3, None).unwrap();
+    out.write_result(&test_ok, &TrOk, Some(&exec_time), b"ignored output", &st).unwrap();
+    out.write_result(&test_failed, &TrFailedMsg("a & b".into()), None, b"line 1\nline 2", &st)
+        .unwrap();
+    out.write_result(&test_allowed_fail, &TestResult::TrAllowedFail, None, b"", &st).unwrap();
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+
+    assert!(s.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(s.contains(r#"failures="1" tests="3" skipped="1" time="1.500">"#));
+    assert!(s.contains(r#"<testcase classname="module" name="ok" time="1.500">"#));
+    assert!(s.contains(r#"<testcase classname="module" name="&lt;failed&gt;" time="0.000">"#));
+    assert!(s.contains(r#"<failure type="assert" message="a &amp; b"/>"#));
+    assert!(s.contains("<system-out>line 1&#10;line 2</system-out>"));
+    assert!(s.contains(r#"<skipped message="failure allowed"/>"#));
+    assert!(!s.contains("allowed_failure"));
+    assert!(!s.contains("ignored output"));
+    assert!(s.ends_with("</testsuite>\n</testsuites>\n"));
+}
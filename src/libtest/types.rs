@@ -2,6 +2,7 @@
 
 use std::borrow::Cow;
 use std::fmt;
+use std::time::Duration;
 
 use super::bench::Bencher;
 use super::options;
@@ -121,6 +122,9 @@ pub struct TestDesc {
     pub should_panic: options::ShouldPanic,
     pub allow_fail: bool,
     pub test_type: TestType,
+    /// Time after which the test is terminated and considered failed,
+    /// overriding the `--test-timeout` command line option.
+    pub timeout: Option<Duration>,
 }
 
 impl TestDesc {
@@ -0,0 +1,5 @@
+fn main() {
+    let x = 1;
+    let _y = 2;
+    println!("{}", x);
+}
@@ -0,0 +1,5 @@
+#![crate_type = "lib"]
+
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
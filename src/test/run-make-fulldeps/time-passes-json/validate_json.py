@@ -0,0 +1,18 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+# Every line is a JSON object describing a pass
+passes = []
+with open(sys.argv[1]) as report:
+    for line in report:
+        record = json.loads(line)
+        assert sorted(record.keys()) == ['depth', 'pass', 'rss_end', 'rss_start', 'time'], record
+        assert isinstance(record['depth'], int) and record['depth'] >= 0, record
+        assert isinstance(record['time'], (int, float)) and record['time'] >= 0, record
+        for rss in (record['rss_start'], record['rss_end']):
+            assert rss is None or isinstance(rss, int), record
+        passes.append(record['pass'])
+
+assert 'parse_crate' in passes, passes
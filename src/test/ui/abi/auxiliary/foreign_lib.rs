@@ -1,6 +1,7 @@
 #![crate_name="foreign_lib"]
 
 #![feature(rustc_private)]
+#![allow(clashing_extern_declarations)]
 
 pub mod rustrt {
     extern crate libc;
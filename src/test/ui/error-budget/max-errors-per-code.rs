@@ -0,0 +1,13 @@
+// Checks that only as many errors with each code are shown as
+// `-Z max-errors-per-code` allows, and that the others are summarized.
+
+// compile-flags: -Z max-errors-per-code=2 -Z error-summary
+
+fn main() {
+    let _a: u32 = "a"; //~ ERROR mismatched types
+    let _b: u32 = "b"; //~ ERROR mismatched types
+    let _c: u32 = "c";
+    let _d: u32 = "d";
+    let _e: u32 = "e";
+    let _f: u32 = missing; //~ ERROR cannot find value `missing` in this scope
+}
@@ -0,0 +1,9 @@
+// Checks that only as many errors are shown as `-Z max-errors` allows, and
+// that only the codes of the errors shown are explained.
+
+// compile-flags: -Z max-errors=1
+
+fn main() {
+    let _a: u32 = "a"; //~ ERROR mismatched types
+    let _b = 1u32.missing();
+}
@@ -0,0 +1,6 @@
+// edition:2018
+// compile-flags: --test
+// check that `#[test] async fn` is feature-gated
+
+#[test]
+async fn it_works() {} //~ ERROR async tests are experimental
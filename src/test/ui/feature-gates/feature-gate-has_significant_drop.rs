@@ -0,0 +1,6 @@
+// check that #[has_significant_drop] is feature-gated
+
+#[has_significant_drop = "reason"] //~ ERROR the `#[has_significant_drop]` attribute is an experimental
+struct Guard;
+
+fn main() {}
@@ -0,0 +1,6 @@
+// check that #[must_not_suspend] is feature-gated
+
+#[must_not_suspend = "reason"] //~ ERROR the `#[must_not_suspend]` attribute is an experimental
+struct Guard;
+
+fn main() {}
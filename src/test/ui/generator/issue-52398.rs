@@ -1,5 +1,6 @@
 // run-pass
 #![allow(unused_variables)]
+#![allow(must_not_suspend)]
 
 #![feature(generators)]
 
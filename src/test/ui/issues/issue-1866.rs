@@ -1,6 +1,7 @@
 // build-pass
 #![allow(dead_code)]
 #![allow(non_camel_case_types)]
+#![allow(clashing_extern_declarations)]
 
 // pretty-expanded FIXME #23616
 
@@ -1,5 +1,6 @@
 // run-pass
 #![allow(dead_code)]
+#![allow(clashing_extern_declarations)]
 // pretty-expanded FIXME #23616
 
 extern {
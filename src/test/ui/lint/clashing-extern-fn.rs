@@ -0,0 +1,34 @@
+// check-pass
+#![crate_type = "lib"]
+
+#[repr(C)]
+pub struct Node {
+    pub next: *const Node,
+    pub value: u32,
+}
+
+pub mod a {
+    extern "C" {
+        pub fn clash(x: u8);
+        pub fn same(x: u8) -> u32;
+        pub fn nullable(x: Option<&u8>);
+        pub fn node(n: *const crate::Node);
+    }
+}
+
+pub mod b {
+    #[repr(C)]
+    pub struct OtherNode {
+        pub next: *const OtherNode,
+        pub value: u32,
+    }
+
+    extern "C" {
+        pub fn clash(x: u64); //~ WARN `clash` redeclared with a different signature
+        pub fn same(x: u8) -> u32;
+        pub fn nullable(x: *const u8);
+        pub fn node(n: *const OtherNode);
+        #[link_name = "same"]
+        pub fn renamed(x: u8); //~ WARN `renamed` redeclares `same` with a different signature
+    }
+}
@@ -0,0 +1,28 @@
+#![deny(dangling_pointers_from_temporaries)]
+
+use std::ffi::CString;
+
+fn use_ptr<T>(_: *const T) {}
+
+fn main() {
+    let bytes: &[u8] = &[1, 2, 3];
+
+    let s = CString::new("hello").unwrap().as_ptr();
+    //~^ ERROR getting the inner pointer of a temporary `std::ffi::CString`
+    let v = bytes.to_vec().as_mut_ptr();
+    //~^ ERROR getting the inner pointer of a temporary `std::vec::Vec<u8>`
+    let b = bytes.to_vec().into_boxed_slice().as_ptr() as *const i8;
+    //~^ ERROR getting the inner pointer of a temporary `std::boxed::Box<[u8]>`
+    let t;
+    t = String::from("hello").as_ptr();
+    //~^ ERROR getting the inner pointer of a temporary `std::string::String`
+
+    // The owners outlive the pointers.
+    let owned = CString::new("hello").unwrap();
+    let ok = owned.as_ptr();
+    use_ptr(CString::new("hello").unwrap().as_ptr());
+    let len = String::from("hello").as_ptr().align_offset(4);
+    let ok2 = "hello".as_ptr();
+
+    let _ = (s, v, b, t, ok, len, ok2);
+}
@@ -0,0 +1,28 @@
+// build-pass
+// compile-flags: -Z stack-frame-size-limit=4096
+// only-x86_64
+
+fn consume<T>(_: &T) {}
+
+fn small() {
+    let buf = [0u8; 1024];
+    consume(&buf);
+}
+
+fn large() {
+    let buf = [0u8; 8192];
+    consume(&buf);
+}
+//~^^^^ WARNING the stack frame of `large` is about 8200 bytes
+
+#[allow(large_stack_frames)]
+fn allowed() {
+    let buf = [0u8; 8192];
+    consume(&buf);
+}
+
+fn main() {
+    small();
+    large();
+    allowed();
+}
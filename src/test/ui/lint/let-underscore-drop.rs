@@ -0,0 +1,38 @@
+// run-pass
+// run-rustfix
+// rustfix-only-machine-applicable
+
+#![feature(has_significant_drop)]
+
+use std::marker::PhantomData;
+use std::sync::{Mutex, MutexGuard, RwLock};
+
+#[has_significant_drop = "dropping a `Token` gives it back"]
+struct Token;
+
+impl Drop for Token {
+    fn drop(&mut self) {}
+}
+
+fn token() -> Token {
+    Token
+}
+
+fn main() {
+    let mutex = Mutex::new(0);
+    let _ = mutex.lock();
+    //~^ WARNING non-binding `let` drops the `std::sync::MutexGuard` right away
+    let rwlock = RwLock::new(0);
+    let _ = rwlock.write().unwrap();
+    //~^ WARNING non-binding `let` drops the `std::sync::RwLockWriteGuard` right away
+    let _ = (token(), 1);
+    //~^ WARNING non-binding `let` drops the `Token` right away
+
+    // Bound values live until the end of the scope.
+    let _guard = mutex.lock();
+    let _token = token();
+    // References don't drop what they point to.
+    let _ = &_token;
+    // Neither does `PhantomData`.
+    let _ = PhantomData::<MutexGuard<'static, i32>>;
+}
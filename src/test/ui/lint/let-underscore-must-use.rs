@@ -0,0 +1,24 @@
+// run-rustfix
+// rustfix-only-machine-applicable
+
+#![deny(let_underscore_must_use)]
+
+#[must_use]
+struct Important;
+
+#[must_use]
+fn compute() -> u32 {
+    0
+}
+
+fn important() -> Important {
+    Important
+}
+
+fn main() {
+    let _ = compute();
+    //~^ ERROR non-binding `let` discards the return value of `compute`
+    let _ = important();
+    //~^ ERROR non-binding `let` discards a value of `#[must_use]` type `Important`
+    let _ = 1 + 1;
+}
@@ -0,0 +1,16 @@
+// Check that lint levels can be set by a lint configuration file, below the
+// lint attributes, and scoped to the files under a path prefix.
+//
+// compile-flags: -Z unstable-options --lint-config {{src-base}}/lint/lint-config/lint-config.txt
+
+#[path = "auxiliary/generated.rs"]
+mod generated;
+
+fn unused() {} //~ ERROR function is never used
+
+#[allow(dead_code)]
+fn allowed() {}
+
+fn main() {
+    let _InappropriateCamelCasing = true; //~ ERROR should have a snake
+}
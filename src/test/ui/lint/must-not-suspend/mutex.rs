@@ -0,0 +1,39 @@
+// edition:2018
+// check-pass
+#![feature(must_not_suspend)]
+
+use std::sync::Mutex;
+
+#[must_not_suspend = "the token must be released before suspending"]
+struct Token;
+
+async fn other() {}
+
+pub async fn held(m: &Mutex<u32>) {
+    let mut guard = m.lock().unwrap(); //~ WARN `std::sync::MutexGuard` held across
+    other().await;
+    *guard += 1;
+}
+
+pub async fn scoped(m: &Mutex<u32>) {
+    {
+        let mut guard = m.lock().unwrap();
+        *guard += 1;
+    }
+    other().await;
+}
+
+pub async fn in_tuple() {
+    let pair = (Token, 1); //~ WARN `Token` held across
+    other().await;
+    drop(pair);
+}
+
+pub async fn allowed() {
+    #[allow(must_not_suspend)]
+    let token = Token;
+    other().await;
+    drop(token);
+}
+
+fn main() {}
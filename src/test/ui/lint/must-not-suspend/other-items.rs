@@ -0,0 +1,11 @@
+#![feature(must_not_suspend)]
+
+#[must_not_suspend] //~ ERROR attribute should be applied to a struct, enum, or union
+fn foo() {}
+
+#[must_not_suspend]
+union Bar {
+    x: u32,
+}
+
+fn main() {}
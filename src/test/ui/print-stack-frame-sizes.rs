@@ -0,0 +1,18 @@
+// compile-flags: -Z print-stack-frame-sizes
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+// only-x86_64
+
+fn consume<T>(_: &T) {}
+
+fn buffers() {
+    let small = [0u8; 16];
+    consume(&small);
+    let large = [0u64; 128];
+    consume(&large);
+}
+
+fn main() {
+    buffers();
+}
@@ -0,0 +1,37 @@
+// edition:2018
+// compile-flags: --test
+// run-flags: --test-threads=1
+// run-fail
+// check-run-results
+
+// ignore-wasm no panic support
+// ignore-emscripten no panic support
+
+#![feature(async_test, test_timeout)]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+// A future that keeps its waker, but is never woken up.
+struct Stalled(Option<Waker>);
+
+impl Future for Stalled {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.0 = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[test]
+async fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+#[test_timeout = "1"]
+async fn it_hangs() {
+    Stalled(None).await
+}
@@ -0,0 +1,50 @@
+// run-pass
+// edition:2018
+// compile-flags: --test
+// ignore-emscripten no threads support
+
+#![feature(async_test)]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+// A future that is woken up from another thread once.
+struct Delay(bool);
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        let waker = cx.waker().clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+#[test]
+async fn it_awaits() {
+    Delay(false).await;
+}
+
+#[test]
+async fn it_returns_ok() -> Result<(), String> {
+    Delay(false).await;
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+async fn it_panics() {
+    Delay(false).await;
+    panic!("boom");
+}
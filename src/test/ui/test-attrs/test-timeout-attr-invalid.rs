@@ -0,0 +1,12 @@
+// compile-flags: --test
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout = "0"]
+//~^ ERROR invalid `#[test_timeout]` attribute
+fn test1() {}
+
+#[test]
+#[test_timeout = "soon"]
+//~^ ERROR invalid `#[test_timeout]` attribute
+fn test2() {}
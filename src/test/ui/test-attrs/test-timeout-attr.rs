@@ -0,0 +1,9 @@
+// run-pass
+// compile-flags: --test
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout = "60"]
+fn test1() {
+    assert!(true);
+}
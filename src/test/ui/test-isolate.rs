@@ -0,0 +1,45 @@
+// no-prefer-dynamic
+// compile-flags: --test
+// run-flags: --test-threads=1 -Zunstable-options --isolate
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+
+// ignore-wasm no panic or subprocess support
+// ignore-emscripten no panic or subprocess support
+// ignore-windows crashes are reported as signals on unix only
+
+#![cfg(test)]
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
+
+#[test]
+fn it_catches_a_panic() {
+    assert!(std::panic::catch_unwind(|| panic!("caught")).is_err());
+}
+
+#[test]
+fn it_joins_a_panicking_thread() {
+    assert!(std::thread::spawn(|| panic!("joined")).join().is_err());
+}
+
+#[test]
+#[should_panic(expected = "dropped")]
+fn it_unwinds() {
+    struct Cleanup;
+    impl Drop for Cleanup {
+        fn drop(&mut self) {
+            println!("cleaned up");
+        }
+    }
+    let _cleanup = Cleanup;
+    panic!("dropped");
+}
+
+#[test]
+fn it_aborts() {
+    std::process::abort();
+}
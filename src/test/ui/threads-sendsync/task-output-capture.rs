@@ -0,0 +1,25 @@
+// run-pass
+// ignore-emscripten no threads support
+
+#![feature(set_stdio)]
+
+use std::io;
+use std::str;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+fn main() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    io::set_output_capture(Some(data.clone()));
+    let res = thread::Builder::new().spawn(|| {
+        println!("Hello from a child thread");
+        thread::spawn(|| panic!("Hello, world!")).join()
+    }).unwrap().join();
+    io::set_output_capture(None);
+    assert!(res.unwrap().is_err());
+
+    let output = data.lock().unwrap();
+    let output = str::from_utf8(&output).unwrap();
+    assert!(output.contains("Hello from a child thread"));
+    assert!(output.contains("Hello, world!"));
+}
@@ -0,0 +1,10 @@
+# The French catalog of the translation tests.
+
+borrowck-assign-borrowed = impossible d'assigner à `{ $desc }` car il est emprunté
+borrowck-borrow-here = `{ $desc }` est emprunté ici
+borrowck-assign-borrowed-here = assignation à `{ $desc }` emprunté ici
+
+typeck-primitive-no-fields = `{ $ty }` est un type primitif et n'a donc pas de champs
+typeck-cannot-deref = le type `{ $ty }` ne peut pas être déréférencé
+typeck-unary-op-unsupported = impossible d'appliquer l'opérateur unaire `{ $op }` au type `{ $ty }`
+typeck-unary-op-unsupported-label = impossible d'appliquer l'opérateur unaire `{ $op }`
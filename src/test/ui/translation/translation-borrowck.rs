@@ -0,0 +1,12 @@
+// Check that the borrow checker errors which have a message identifier are
+// translated with the catalog of `-Z translation-lang`.
+//
+// compile-flags: -Z translation-lang=fr -Z translation-dir={{src-base}}/translation/locale
+
+fn main() {
+    let mut x = 0;
+    let r = &x;
+    x = 1; //~ ERROR impossible d'assigner à `x` car il est emprunté
+    drop(r);
+    drop(x);
+}
@@ -0,0 +1,12 @@
+// Check that the type checking errors which have a message identifier are
+// translated with the catalog of `-Z translation-lang`, and that the messages
+// which have none are left in English.
+//
+// compile-flags: -Z translation-lang=fr -Z translation-dir={{src-base}}/translation/locale
+
+fn main() {
+    let x = 1u32;
+    let _ = x.foo; //~ ERROR `u32` est un type primitif
+    let _ = *x; //~ ERROR le type `u32` ne peut pas être déréférencé
+    let _ = -x; //~ ERROR impossible d'appliquer l'opérateur unaire `-` au type `u32`
+}
@@ -0,0 +1 @@
+pub const BAR: &str = "bar";
@@ -0,0 +1,8 @@
+// Check that the crates passed with `--extern` which are never used are reported.
+//
+// aux-crate:bar=bar.rs
+// edition:2018
+
+#![deny(unused_crate_dependencies)]
+
+fn main() {}
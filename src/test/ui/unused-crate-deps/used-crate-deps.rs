@@ -0,0 +1,15 @@
+// Check that the crates passed with `--extern` which are used, even only by
+// an anonymous import, are not reported.
+//
+// check-pass
+// aux-crate:bar=bar.rs
+// aux-crate:baz=baz.rs
+// edition:2018
+
+#![deny(unused_crate_dependencies)]
+
+use baz as _;
+
+fn main() {
+    let _ = bar::BAR;
+}
@@ -432,17 +432,26 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         logfile: config.logfile.clone(),
         run_tests: true,
         bench_benchmarks: true,
+        save_baseline: None,
+        baseline: None,
         nocapture: match env::var("RUST_TEST_NOCAPTURE") {
             Ok(val) => &val != "0",
             Err(_) => false,
         },
+        capture: test::CaptureMode::Local,
         color: config.color,
         test_threads: None,
+        test_timeout: None,
         skip: vec![],
+        shard: None,
+        retries: 0,
         list: false,
         options: test::Options::new(),
         time_options: None,
         force_run_in_process: false,
+        isolate: false,
+        shuffle: false,
+        shuffle_seed: None,
     }
 }
 
@@ -615,6 +624,7 @@ fn make_test(config: &Config, testpaths: &TestPaths, inputs: &Stamp) -> Vec<test
                     should_panic,
                     allow_fail: false,
                     test_type: test::TestType::Unknown,
+                    timeout: None,
                 },
                 testfn: make_test_closure(config, testpaths, revision),
             }